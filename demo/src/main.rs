@@ -1,71 +1,330 @@
-use language::{glyphs::*, letters::*};
+use clap::{Parser, ValueEnum};
+use language::{
+    colours::{Colour, Colouring, Palette},
+    dxf::*,
+    glyphs::{Base, Modifier},
+    layout::*,
+    numbers::*,
+    pdf::*,
+    plotter::*,
+    sentences::*,
+    shapes::*,
+    svg::*,
+    tikz::*,
+};
 use plotters::prelude::*;
-use std::io;
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    std::process::Command::new("clear").status().unwrap();
-    let mut word = String::new();
-    println!("Enter word: ");
-    io::stdin()
-        .read_line(&mut word)
-        .expect("Smart user. Valid word.");
-    let root = BitMapBackend::new("gallifreyan-message.png", (640, 640)).into_drawing_area();
-    root.fill(&WHITE)?;
+use std::error::Error;
+use std::io::{self, IsTerminal, Read};
+use std::path::PathBuf;
+
+/// Every consonant base, matched by variant when colouring.
+const BASES: [Base; 6] = [
+    Base::Moon(0.0),
+    Base::Core,
+    Base::Crescent,
+    Base::Full,
+    Base::Quarter,
+    Base::New,
+];
+/// Every consonant modifier, matched by variant when colouring.
+const MODIFIERS: [Modifier; 8] = [
+    Modifier::Dot1,
+    Modifier::Dot2,
+    Modifier::Dot3,
+    Modifier::Dot4,
+    Modifier::VowelLine1(0.0),
+    Modifier::Line1,
+    Modifier::Line2,
+    Modifier::Line3,
+];
+
+/// Transcribes a message to Sherman's Circular Gallifreyan and draws it to an image.
+#[derive(Parser)]
+#[command(version)]
+struct Args {
+    /// The message to transcribe. Read from the input file or stdin when missing.
+    text: Option<String>,
+
+    /// Read the message from a file instead.
+    #[arg(short, long, conflicts_with = "text")]
+    input: Option<PathBuf>,
+
+    /// The image to write.
+    #[arg(short, long, default_value = "gallifreyan-message.png")]
+    output: PathBuf,
+
+    /// The image format. Guessed from the output extension when missing.
+    #[arg(short, long, value_enum)]
+    format: Option<Format>,
+
+    /// The width and height of the image in pixels.
+    #[arg(short, long, default_value_t = 640)]
+    size: u32,
+
+    /// The colour of the strokes, as #rrggbb. DXF, G-code and HPGL output ignore it and the other
+    /// colours, DXF drawing every part in the colour of its layer.
+    #[arg(long, default_value = "#0000ff")]
+    stroke: Colour,
+
+    /// The colour of the background, as #rrggbb.
+    #[arg(long, default_value = "#ffffff")]
+    background: Colour,
+
+    /// The colour of the vowels, as #rrggbb. Drawn in the stroke colour when missing.
+    #[arg(long)]
+    vowel_colour: Option<Colour>,
+
+    /// The colour of the consonant bases, as #rrggbb. Drawn in the stroke colour when missing.
+    #[arg(long)]
+    base_colour: Option<Colour>,
+
+    /// The colour of the consonant dots and lines, as #rrggbb. Drawn in the stroke colour when
+    /// missing.
+    #[arg(long)]
+    modifier_colour: Option<Colour>,
+
+    /// The width of the strokes in pixels.
+    #[arg(short, long, default_value_t = 1)]
+    line_width: u32,
+
+    /// Write the message under the drawing. Only PDF documents have a caption.
+    #[arg(long)]
+    caption: bool,
+
+    /// Stack consecutive consonants of the same base in one position, keeping long words small.
+    #[arg(long)]
+    stack: bool,
+
+    /// Read the message from stdin without clearing the terminal or prompting for it.
+    #[arg(short, long)]
+    non_interactive: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Png,
+    Svg,
+    Pdf,
+    Gcode,
+    Hpgl,
+    Dxf,
+    Tikz,
+}
+
+fn to_rgb(colour: Colour) -> RGBColor {
+    RGBColor(colour.0, colour.1, colour.2)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let message = read_message(&args)?;
+    let message = message.trim_end_matches(['\r', '\n']);
+
+    let palette = Palette {
+        vowels: args.vowel_colour,
+        bases: args.base_colour.map_or(Vec::new(), |colour| {
+            BASES.iter().map(|base| (*base, colour)).collect()
+        }),
+        modifiers: args.modifier_colour.map_or(Vec::new(), |colour| {
+            MODIFIERS
+                .iter()
+                .map(|modifier| (*modifier, colour))
+                .collect()
+        }),
+        ..Palette::new(args.stroke)
+    };
+
+    let (shapes, bound, layout) = match message.trim().parse::<GallifreyanNumber>() {
+        Ok(gallifreyan_number) => (
+            gallifreyan_number
+                .to_shapes()
+                .into_iter()
+                .map(|shape| (shape, args.stroke))
+                .collect::<Vec<(Shape, Colour)>>(),
+            gallifreyan_number.size() + 3.0,
+            None,
+        ),
+        Err(_) => {
+            let gallifreyan_sentence = message
+                .parse::<GallifreyanSentence>()?
+                .with_stacking(args.stack);
+            let layout = gallifreyan_sentence.to_layout();
+            (
+                layout
+                    .elements()
+                    .iter()
+                    .map(|element| (element.shape, palette.colour(element)))
+                    .collect(),
+                gallifreyan_sentence.size() + 1.0,
+                Some(layout),
+            )
+        }
+    };
+
+    let format = args.format.unwrap_or_else(|| {
+        match args
+            .output
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some(extension) if extension.eq_ignore_ascii_case("svg") => Format::Svg,
+            Some(extension) if extension.eq_ignore_ascii_case("pdf") => Format::Pdf,
+            Some(extension) if ["gcode", "nc"].contains(&extension.to_lowercase().as_str()) => {
+                Format::Gcode
+            }
+            Some(extension) if ["hpgl", "plt"].contains(&extension.to_lowercase().as_str()) => {
+                Format::Hpgl
+            }
+            Some(extension) if extension.eq_ignore_ascii_case("dxf") => Format::Dxf,
+            Some(extension) if extension.eq_ignore_ascii_case("tex") => Format::Tikz,
+            _ => Format::Png,
+        }
+    });
+
+    match format {
+        Format::Png => draw_png(&args, &shapes, bound as f32),
+        Format::Svg => draw_svg(&args, &shapes, bound),
+        Format::Pdf => draw_pdf(&args, &shapes, bound, message),
+        Format::Gcode => draw_plot(&args, &shapes, bound, shapes_to_gcode),
+        Format::Hpgl => draw_plot(&args, &shapes, bound, shapes_to_hpgl),
+        Format::Dxf => draw_dxf(&args, &shapes, layout.as_ref()),
+        Format::Tikz => draw_tikz(&args, &shapes),
+    }
+}
+
+fn read_message(args: &Args) -> Result<String, Box<dyn Error>> {
+    if let Some(text) = &args.text {
+        return Ok(text.clone());
+    }
+    if let Some(input) = &args.input {
+        return Ok(std::fs::read_to_string(input)?);
+    }
+
+    let mut message = String::new();
+    match !args.non_interactive && io::stdin().is_terminal() {
+        true => {
+            std::process::Command::new("clear").status()?;
+            println!("Enter message: ");
+            io::stdin().read_line(&mut message)?;
+        }
+        false => {
+            io::stdin().read_to_string(&mut message)?;
+        }
+    }
+
+    Ok(message)
+}
+
+fn draw_png(args: &Args, shapes: &[(Shape, Colour)], bound: f32) -> Result<(), Box<dyn Error>> {
+    let root = BitMapBackend::new(&args.output, (args.size, args.size)).into_drawing_area();
+    root.fill(&to_rgb(args.background))?;
     let mut chart = ChartBuilder::on(&root)
         .margin(5)
-        .build_cartesian_2d(-20f32..20f32, -20f32..20f32)?;
+        .build_cartesian_2d(-bound..bound, -bound..bound)?;
+
+    shapes.iter().try_for_each(|(shape, colour)| {
+        let stroke = to_rgb(*colour);
+        match shape.is_dot() {
+            true => chart.draw_series(
+                LineSeries::new(shape.to_polyline(), stroke.filled())
+                    .point_size(2 * args.line_width),
+            ),
+            false => chart.draw_series(LineSeries::new(
+                shape.to_polyline(),
+                stroke.stroke_width(args.line_width),
+            )),
+        }
+        .map(|_| ())
+    })?;
+
+    root.present()?;
+    Ok(())
+}
+
+fn draw_svg(args: &Args, shapes: &[(Shape, Colour)], bound: f64) -> Result<(), Box<dyn Error>> {
+    let units_per_pixel = 2.0 * bound / args.size as f64;
+    let options = SvgOptions {
+        colour: args.stroke.to_hex(),
+        background: Some(args.background.to_hex()),
+        stroke_width: args.line_width as f64 * units_per_pixel,
+        dot_radius: 2.0 * args.line_width as f64 * units_per_pixel,
+    };
+
+    std::fs::write(
+        &args.output,
+        coloured_shapes_to_svg(shapes, bound, &options),
+    )?;
+    Ok(())
+}
 
-    let gallifreyan_word = GallifreyanWord::from(&word.trim_end_matches("\n"));
+fn draw_pdf(
+    args: &Args,
+    shapes: &[(Shape, Colour)],
+    bound: f64,
+    message: &str,
+) -> Result<(), Box<dyn Error>> {
+    let options = PdfOptions {
+        colour: args.stroke,
+        background: Some(args.background),
+        stroke_width: args.line_width as f64,
+        dot_radius: 2.0 * args.line_width as f64,
+        caption: args.caption.then(|| message.to_string()),
+        ..PdfOptions::default()
+    };
 
-    gallifreyan_word
-        .to_gallifreyan_characters()
+    std::fs::write(
+        &args.output,
+        coloured_shapes_to_pdf(shapes, bound, &options),
+    )?;
+    Ok(())
+}
+
+fn draw_plot(
+    args: &Args,
+    shapes: &[(Shape, Colour)],
+    bound: f64,
+    to_program: fn(&[Shape], f64, &PlotterOptions) -> String,
+) -> Result<(), Box<dyn Error>> {
+    let shapes = shapes
         .iter()
-        .for_each(|gallifreyan_character| {
-            chart
-                .draw_series(LineSeries::new(
-                    gallifreyan_character.draw_base().to_vec(),
-                    BLUE,
-                ))
-                .unwrap();
-
-            match gallifreyan_character.modifier {
-                Some(
-                    Modifier::Line1 | Modifier::VowelLine1(_) | Modifier::Line2 | Modifier::Line3,
-                ) => {
-                    gallifreyan_character
-                        .draw_modifier()
-                        .expect("Already checked if modifier exists.")
-                        .iter()
-                        .for_each(|drawing| {
-                            chart
-                                .draw_series(LineSeries::new(drawing.to_vec(), BLUE))
-                                .unwrap();
-                        });
-                }
-                Some(Modifier::Dot1 | Modifier::Dot2 | Modifier::Dot3 | Modifier::Dot4) => {
-                    gallifreyan_character
-                        .draw_modifier()
-                        .expect("Already checked if modifier exists.")
-                        .iter()
-                        .for_each(|drawing| {
-                            chart
-                                .draw_series(
-                                    LineSeries::new(drawing.to_vec(), BLUE.filled()).point_size(2),
-                                )
-                                .unwrap();
-                        });
-                }
-                None => (),
-            }
-        });
-
-    gallifreyan_word
-        .draw_edges()
-        .into_iter()
-        .for_each(|drawing| {
-            chart
-                .draw_series(LineSeries::new(drawing.to_vec(), BLUE))
-                .unwrap();
-        });
+        .map(|(shape, _)| *shape)
+        .collect::<Vec<Shape>>();
+
+    std::fs::write(
+        &args.output,
+        to_program(&shapes, bound, &PlotterOptions::default()),
+    )?;
+    Ok(())
+}
+
+fn draw_dxf(
+    args: &Args,
+    shapes: &[(Shape, Colour)],
+    layout: Option<&Layout>,
+) -> Result<(), Box<dyn Error>> {
+    let dxf = match layout {
+        Some(layout) => layout_to_dxf(layout),
+        None => shapes_to_dxf(
+            &shapes
+                .iter()
+                .map(|(shape, _)| *shape)
+                .collect::<Vec<Shape>>(),
+        ),
+    };
+
+    std::fs::write(&args.output, dxf)?;
+    Ok(())
+}
+
+fn draw_tikz(args: &Args, shapes: &[(Shape, Colour)]) -> Result<(), Box<dyn Error>> {
+    let options = TikzOptions {
+        line_width: args.line_width as f64,
+        dot_radius: 2.0 * args.line_width as f64,
+        background: Some(args.background),
+        standalone: true,
+        ..TikzOptions::default()
+    };
+
+    std::fs::write(&args.output, coloured_shapes_to_tikz(shapes, &options))?;
     Ok(())
 }
@@ -0,0 +1,166 @@
+use crate::glyphs::{Base, Modifier};
+use crate::layout::{Element, Role};
+use core::fmt;
+use std::error::Error;
+use std::fmt::Display;
+use std::mem;
+use std::str::FromStr;
+
+/// The error returned when text is not a `#rrggbb` colour.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseColourError {
+    text: String,
+}
+
+impl ParseColourError {
+    /// The text that could not be read as a colour.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Display for ParseColourError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is not a #rrggbb colour", self.text)
+    }
+}
+
+impl Error for ParseColourError {}
+
+/// A colour, as its red, green and blue channels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Colour(pub u8, pub u8, pub u8);
+
+impl FromStr for Colour {
+    type Err = ParseColourError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseColourError {
+            text: s.to_string(),
+        };
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if hex.len() != 6 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+        let channel =
+            |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).map_err(|_| error());
+
+        Ok(Colour(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+impl Colour {
+    pub const BLACK: Colour = Colour(0x00, 0x00, 0x00);
+
+    /// The colour as `#rrggbb`.
+    pub fn to_hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Picks the colour every element of a layout is drawn in.
+pub trait Colouring {
+    fn colour(&self, element: &Element) -> Colour;
+}
+
+/// Draws everything in the one colour.
+impl Colouring for Colour {
+    fn colour(&self, _: &Element) -> Colour {
+        *self
+    }
+}
+
+impl<F: Fn(&Element) -> Colour> Colouring for F {
+    fn colour(&self, element: &Element) -> Colour {
+        self(element)
+    }
+}
+
+/// Colours the parts of the letters, drawing everything else in `ink`.
+///
+/// An element takes the colour of its letter index if there is one, then that of its base or
+/// modifier variant, then that of vowels or consonants. The payloads of `Base::Moon` and
+/// `Modifier::VowelLine1` are ignored when matching variants.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette {
+    pub ink: Colour,
+    /// The colours of letters, by their index in their word.
+    pub letters: Vec<(usize, Colour)>,
+    /// The colours of consonant bases, by variant.
+    pub bases: Vec<(Base, Colour)>,
+    /// The colours of consonant modifiers, by variant.
+    pub modifiers: Vec<(Modifier, Colour)>,
+    pub vowels: Option<Colour>,
+    pub consonants: Option<Colour>,
+}
+
+impl Palette {
+    /// The palette drawing everything in `ink`.
+    pub fn new(ink: Colour) -> Palette {
+        Palette {
+            ink,
+            letters: Vec::new(),
+            bases: Vec::new(),
+            modifiers: Vec::new(),
+            vowels: None,
+            consonants: None,
+        }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::new(Colour::BLACK)
+    }
+}
+
+impl Colouring for Palette {
+    fn colour(&self, element: &Element) -> Colour {
+        let letter = element.index.and_then(|index| {
+            self.letters
+                .iter()
+                .find(|(letter_index, _)| *letter_index == index)
+                .map(|(_, colour)| *colour)
+        });
+        let variant = match element.role {
+            Role::Base => element.base.and_then(|base| {
+                self.bases
+                    .iter()
+                    .find(|(other, _)| mem::discriminant(other) == mem::discriminant(&base))
+                    .map(|(_, colour)| *colour)
+            }),
+            Role::Modifier => element.modifier.and_then(|modifier| {
+                self.modifiers
+                    .iter()
+                    .find(|(other, _)| mem::discriminant(other) == mem::discriminant(&modifier))
+                    .map(|(_, colour)| *colour)
+            }),
+            _ => None,
+        };
+        let kind = match element.role {
+            Role::Vowel => self.vowels,
+            Role::Base | Role::Modifier => self.consonants,
+            _ => None,
+        };
+
+        letter.or(variant).or(kind).unwrap_or(self.ink)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colours_parse_from_six_hex_digits() {
+        assert_eq!("#00ff7F".parse(), Ok(Colour(0x00, 0xff, 0x7f)));
+        assert_eq!("102030".parse(), Ok(Colour(0x10, 0x20, 0x30)));
+    }
+
+    #[test]
+    fn malformed_colours_are_errors() {
+        ["##ffffff", "#+1+2+3", "#fffff", "#fffffff", "#ffffé", ""]
+            .iter()
+            .for_each(|text| assert!(text.parse::<Colour>().is_err(), "{text}"));
+    }
+}
@@ -0,0 +1,534 @@
+use crate::glyphs::Modifier;
+use crate::letters::GallifreyanWord;
+use crate::shapes::{BoundingBox, Shape};
+use geomath::prelude::coordinates::Polar;
+use geomath::prelude::Metric;
+use geomath::vector::Vector2;
+use std::f64::consts::PI;
+use std::iter;
+
+/// How close, in letter sizes, a connecting line can pass to a stroke it does not start on.
+const CLEARANCE: f64 = 0.3;
+/// How far, in letter sizes, a connecting line can stay close to the letter it leaves.
+const DEPARTURE: f64 = 0.6;
+/// The distance, in letter sizes, between the points of a line checked for clearance.
+const SAMPLE_STEP: f64 = 0.05;
+/// How many times a line running on to the word circle can turn further away from its stub, on
+/// either side, when its way is blocked.
+const FALLBACK_TURNS: usize = 5;
+const FALLBACK_TURN_STEP: f64 = PI / 12.0;
+
+/// A line of a line modifier, drawn as a stub until it is connected.
+struct LineEnd {
+    word: usize,
+    character: usize,
+    letter_size: f64,
+    stub: (Vector2, Vector2),
+}
+
+impl LineEnd {
+    fn owner(&self) -> (usize, usize) {
+        (self.word, self.character)
+    }
+
+    fn anchor(&self) -> Vector2 {
+        self.stub.0
+    }
+}
+
+/// A stroke connecting lines keep clear of, with the word and character drawing it, if any.
+struct Obstacle {
+    shape: Shape,
+    bounds: BoundingBox,
+    owner: Option<(usize, usize)>,
+}
+
+impl Obstacle {
+    fn new(shape: Shape, owner: Option<(usize, usize)>) -> Obstacle {
+        Obstacle {
+            shape,
+            bounds: shape.bounds(),
+            owner,
+        }
+    }
+}
+
+/// The modifier shapes of every character of `words`, each word being drawn around its origin,
+/// with the lines of the line modifiers connected.
+///
+/// Lines of different characters, in the same word or not, are paired up shortest first whenever
+/// the segment between them keeps clear of every other stroke and is no longer than they would be
+/// drawn unpaired: the longer of their ways to the word circle within a word, and both of them
+/// across words. Each line of a pair draws its half of the segment. A line left without a partner
+/// runs on to its word circle instead, turning away from its stub if its way is blocked, and keeps
+/// its stub when there is no clear way there either. No line is routed so as to take the last way
+/// to the word circle away from another. The shapes are relative to the origin of their word and
+/// in the order of
+/// [`GallifreyanWord::to_gallifreyan_characters`].
+pub(crate) fn connected_modifier_shapes(
+    words: &[(Vector2, &GallifreyanWord)],
+) -> Vec<Vec<Vec<Shape>>> {
+    let mut obstacles = Vec::new();
+    let mut ends = Vec::new();
+    let mut modifier_shapes = words
+        .iter()
+        .enumerate()
+        .map(|(word_index, (origin, word))| {
+            word.to_gallifreyan_characters()
+                .iter()
+                .enumerate()
+                .map(|(index, character)| {
+                    let owner = Some((word_index, index));
+                    let modifier_shapes = character.modifier_shapes();
+                    obstacles.extend(
+                        character
+                            .base_shapes()
+                            .into_iter()
+                            .map(|shape| Obstacle::new(shape.translate(*origin), owner)),
+                    );
+
+                    match character.modifier {
+                        Some(Modifier::Line1 | Modifier::Line2 | Modifier::Line3) => {
+                            ends.extend(modifier_shapes.iter().filter_map(|shape| match *shape {
+                                Shape::Segment { start, end } => Some(LineEnd {
+                                    word: word_index,
+                                    character: index,
+                                    letter_size: character.size,
+                                    stub: (start + *origin, end + *origin),
+                                }),
+                                _ => None,
+                            }));
+                            Vec::new()
+                        }
+                        _ => {
+                            obstacles.extend(
+                                modifier_shapes
+                                    .iter()
+                                    .map(|shape| Obstacle::new(shape.translate(*origin), owner)),
+                            );
+                            modifier_shapes
+                        }
+                    }
+                })
+                .collect::<Vec<Vec<Shape>>>()
+        })
+        .collect::<Vec<Vec<Vec<Shape>>>>();
+
+    let mut pairs = (0..ends.len())
+        .flat_map(|first| (first + 1..ends.len()).map(move |second| (first, second)))
+        .filter(|(first, second)| ends[*first].owner() != ends[*second].owner())
+        .collect::<Vec<(usize, usize)>>();
+    let pair_length =
+        |(first, second): &(usize, usize)| ends[*first].anchor().distance(&ends[*second].anchor());
+    pairs.sort_by(|pair1, pair2| pair_length(pair1).total_cmp(&pair_length(pair2)));
+
+    let mut router = Router::new(words, &ends, obstacles);
+    // How far each line would have to run on to reach its word circle past the letters alone.
+    let reaches = (0..ends.len())
+        .map(|index| {
+            router
+                .way(index)
+                .map_or(f64::INFINITY, |way| way.0.distance(&way.1))
+        })
+        .collect::<Vec<f64>>();
+
+    let mut lines: Vec<Option<(Vector2, Vector2)>> = vec![None; ends.len()];
+    pairs.into_iter().for_each(|(first, second)| {
+        let (end1, end2) = (&ends[first], &ends[second]);
+        let segment = (end1.anchor(), end2.anchor());
+        let longest = match end1.word == end2.word {
+            true => reaches[first].max(reaches[second]),
+            false => reaches[first] + reaches[second],
+        };
+
+        if lines[first].is_none()
+            && lines[second].is_none()
+            && pair_length(&(first, second)) <= longest
+            && is_clear(
+                segment,
+                &[end1.owner(), end2.owner()],
+                &router.obstacles,
+                end1.letter_size.min(end2.letter_size),
+            )
+            && router.route(segment, &[first, second])
+        {
+            let middle = (segment.0 + segment.1) / 2.0;
+            lines[first] = Some((segment.0, middle));
+            lines[second] = Some((segment.1, middle));
+        }
+    });
+
+    (0..ends.len()).for_each(|index| {
+        let end = &ends[index];
+        let line = lines[index].unwrap_or_else(|| {
+            let ways = router
+                .clear_ways(index)
+                .map(|(_, way)| way)
+                .collect::<Vec<(Vector2, Vector2)>>();
+
+            match ways.iter().find(|way| router.route(**way, &[index])) {
+                Some(way) => *way,
+                // Every way left blocks another line, or there is none.
+                None => {
+                    let line = ways.first().copied().unwrap_or(end.stub);
+                    router.block(line);
+                    router.routed[index] = true;
+                    line
+                }
+            }
+        });
+        let origin = words[end.word].0;
+
+        modifier_shapes[end.word][end.character].push(Shape::Segment {
+            start: line.0 - origin,
+            end: line.1 - origin,
+        });
+    });
+
+    modifier_shapes
+}
+
+/// Keeps track of the strokes drawn so far and of a clear way to the word circle for every line
+/// not routed yet, so that routing a line never takes the last way away from another one.
+struct Router<'a> {
+    ends: &'a [LineEnd],
+    obstacles: Vec<Obstacle>,
+    /// The lines from each line to the drawn part of its word circle, clear of the letters or not.
+    candidates: Vec<Vec<(Vector2, Vector2)>>,
+    /// The first candidate of each line that was clear when last checked. As strokes are only
+    /// ever added, the candidates before it are never clear again.
+    ways: Vec<Option<usize>>,
+    routed: Vec<bool>,
+}
+
+impl<'a> Router<'a> {
+    fn new(
+        words: &[(Vector2, &GallifreyanWord)],
+        ends: &'a [LineEnd],
+        obstacles: Vec<Obstacle>,
+    ) -> Router<'a> {
+        let edges = words
+            .iter()
+            .map(|(origin, word)| {
+                word.edge_shapes()
+                    .iter()
+                    .map(|shape| shape.translate(*origin))
+                    .collect::<Vec<Shape>>()
+            })
+            .collect::<Vec<Vec<Shape>>>();
+        let candidates = ends
+            .iter()
+            .map(|end| Self::candidates(end, words[end.word], &edges[end.word]))
+            .collect();
+        let mut router = Router {
+            ends,
+            obstacles,
+            candidates,
+            ways: vec![Some(0); ends.len()],
+            routed: vec![false; ends.len()],
+        };
+        router.ways = (0..ends.len())
+            .map(|index| {
+                router
+                    .clear_ways(index)
+                    .next()
+                    .map(|(candidate, _)| candidate)
+            })
+            .collect();
+        router
+    }
+
+    /// The lines from `end` to the drawn part of the circle of `word`, given by its `edges`,
+    /// turning further and further away from its stub.
+    fn candidates(
+        end: &LineEnd,
+        (origin, word): (Vector2, &GallifreyanWord),
+        edges: &[Shape],
+    ) -> Vec<(Vector2, Vector2)> {
+        let heading = (end.stub.1 - end.stub.0).phi();
+        let from_origin = end.anchor() - origin;
+
+        iter::once(0.0)
+            .chain((1..=FALLBACK_TURNS).flat_map(|step| {
+                let turn = step as f64 * FALLBACK_TURN_STEP;
+                [turn, -turn]
+            }))
+            .filter_map(|turn| {
+                let direction = Vector2::from_polar(1.0, heading + turn);
+                let along = from_origin.dot(&direction);
+                let discriminant = along.powi(2) - from_origin.magnitude2() + word.size().powi(2);
+                let length = -along + discriminant.max(0.0).sqrt();
+                let line = (end.anchor(), end.anchor() + direction * length);
+
+                (discriminant >= 0.0
+                    && length > 0.0
+                    && edges
+                        .iter()
+                        .any(|edge| edge.distance(line.1) < 1e-6 * word.size()))
+                .then_some(line)
+            })
+            .collect()
+    }
+
+    /// The way of the line `index` to its word circle when it last had one.
+    fn way(&self, index: usize) -> Option<(Vector2, Vector2)> {
+        self.ways[index].map(|candidate| self.candidates[index][candidate])
+    }
+
+    /// The candidate lines of the line `index`, from its last way on, keeping clear of the strokes
+    /// drawn so far, along with their places among the candidates.
+    fn clear_ways(&self, index: usize) -> impl Iterator<Item = (usize, (Vector2, Vector2))> + '_ {
+        let end = &self.ends[index];
+
+        self.candidates[index]
+            .iter()
+            .copied()
+            .enumerate()
+            .skip(self.ways[index].unwrap_or(self.candidates[index].len()))
+            .filter(move |(_, line)| {
+                is_clear(*line, &[end.owner()], &self.obstacles, end.letter_size)
+            })
+    }
+
+    /// Draws `segment` for the lines `routed` if every other line not routed yet that had a way
+    /// to its word circle still has one.
+    fn route(&mut self, segment: (Vector2, Vector2), routed: &[usize]) -> bool {
+        self.block(segment);
+
+        let ways = (0..self.ends.len())
+            .filter(|index| !self.routed[*index] && !routed.contains(index))
+            .filter(|index| {
+                self.way(*index).is_some_and(|way| {
+                    segment_distance(way, segment) < CLEARANCE * self.ends[*index].letter_size
+                })
+            })
+            .map(|index| {
+                let way = self.clear_ways(index).next();
+                (index, way.map(|(candidate, _)| candidate))
+            })
+            .collect::<Vec<(usize, Option<usize>)>>();
+
+        match ways.iter().all(|(_, way)| way.is_some()) {
+            true => {
+                ways.into_iter()
+                    .for_each(|(index, way)| self.ways[index] = way);
+                routed.iter().for_each(|index| self.routed[*index] = true);
+                true
+            }
+            false => {
+                self.obstacles.pop();
+                false
+            }
+        }
+    }
+
+    fn block(&mut self, segment: (Vector2, Vector2)) {
+        self.obstacles.push(Obstacle::new(
+            Shape::Segment {
+                start: segment.0,
+                end: segment.1,
+            },
+            None,
+        ));
+    }
+}
+
+/// The shortest distance between two segments.
+fn segment_distance(first: (Vector2, Vector2), second: (Vector2, Vector2)) -> f64 {
+    let side = |point: Vector2, (start, end): (Vector2, Vector2)| {
+        let (along, across) = (end - start, point - start);
+        along.x * across.y - along.y * across.x
+    };
+    let crossing = side(second.0, first) * side(second.1, first) < 0.0
+        && side(first.0, second) * side(first.1, second) < 0.0;
+    let segment = |(start, end): (Vector2, Vector2)| Shape::Segment { start, end };
+
+    match crossing {
+        true => 0.0,
+        false => [
+            segment(first).distance(second.0),
+            segment(first).distance(second.1),
+            segment(second).distance(first.0),
+            segment(second).distance(first.1),
+        ]
+        .into_iter()
+        .fold(f64::INFINITY, f64::min),
+    }
+}
+
+/// Whether `segment` keeps clear of every obstacle, only coming close to the strokes of `owners`
+/// where it leaves or reaches them.
+///
+/// The segment is walked from its start, skipping ahead by as much as the nearest obstacle is
+/// further than the clearance, and by at least `SAMPLE_STEP` letter sizes.
+fn is_clear(
+    segment: (Vector2, Vector2),
+    owners: &[(usize, usize)],
+    obstacles: &[Obstacle],
+    letter_size: f64,
+) -> bool {
+    let (clearance, departure) = (CLEARANCE * letter_size, DEPARTURE * letter_size);
+    let length = segment.0.distance(&segment.1);
+    let direction = match length > 0.0 {
+        true => (segment.1 - segment.0) / length,
+        false => Vector2::new(0.0, 0.0),
+    };
+    let reach = BoundingBox::point(segment.0)
+        .union(&BoundingBox::point(segment.1))
+        .expand(clearance);
+    let nearby = obstacles
+        .iter()
+        .filter(|obstacle| obstacle.bounds.intersects(&reach))
+        .collect::<Vec<&Obstacle>>();
+    let mut along = 0.0;
+
+    loop {
+        let point = segment.0 + direction * along;
+        let leaving = [along, length - along];
+        let distance = nearby
+            .iter()
+            .filter(|obstacle| {
+                !owners
+                    .iter()
+                    .zip(leaving)
+                    .any(|(owner, distance)| obstacle.owner == Some(*owner) && distance < departure)
+            })
+            .map(|obstacle| obstacle.shape.distance(point))
+            .fold(f64::INFINITY, f64::min);
+
+        if distance < clearance {
+            return false;
+        }
+        if along >= length {
+            return true;
+        }
+        // The strokes of the first owner are only kept clear of past the departure.
+        let next_check = match along < departure {
+            true => departure.min(length),
+            false => length,
+        };
+        along = (along + (distance - clearance).max(SAMPLE_STEP * letter_size)).min(next_check);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::letters::GallifreyanLetter;
+    use crate::sentences::GallifreyanSentence;
+
+    /// The connected lines of `words`, in sentence coordinates, with the word and character
+    /// drawing them.
+    fn lines(words: &[(Vector2, &GallifreyanWord)]) -> Vec<((usize, usize), Vector2, Vector2)> {
+        connected_modifier_shapes(words)
+            .iter()
+            .zip(words)
+            .enumerate()
+            .flat_map(|(word, (characters, (origin, _)))| {
+                characters
+                    .iter()
+                    .enumerate()
+                    .flat_map(move |(character, shapes)| {
+                        shapes.iter().filter_map(move |shape| match *shape {
+                            Shape::Segment { start, end } => {
+                                Some(((word, character), start + *origin, end + *origin))
+                            }
+                            _ => None,
+                        })
+                    })
+            })
+            .collect()
+    }
+
+    /// The pairs of lines meeting halfway, by the characters drawing them.
+    fn pairs(
+        lines: &[((usize, usize), Vector2, Vector2)],
+    ) -> Vec<((usize, usize), (usize, usize))> {
+        (0..lines.len())
+            .flat_map(|first| (first + 1..lines.len()).map(move |second| (first, second)))
+            .filter(|(first, second)| lines[*first].2.distance(&lines[*second].2) < 1e-9)
+            .map(|(first, second)| (lines[first].0, lines[second].0))
+            .collect()
+    }
+
+    #[test]
+    fn lines_pair_up_with_other_characters() {
+        let word = "nm".parse::<GallifreyanWord>().unwrap();
+        let lines = lines(&[(Vector2::new(0.0, 0.0), &word)]);
+        let on_word_circle = lines
+            .iter()
+            .filter(|(_, _, end)| (end.rho() - word.size()).abs() < 1e-9)
+            .count();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(pairs(&lines), [((0, 0), (0, 1))]);
+        assert_eq!(on_word_circle, 2);
+    }
+
+    #[test]
+    fn lines_pair_up_across_words() {
+        let sentence = "tv swim".parse::<GallifreyanSentence>().unwrap();
+        let words = sentence
+            .word_origins()
+            .iter()
+            .copied()
+            .zip(sentence.words())
+            .collect::<Vec<(Vector2, &GallifreyanWord)>>();
+
+        assert!(pairs(&lines(&words))
+            .iter()
+            .any(|((word1, _), (word2, _))| word1 != word2));
+    }
+
+    #[test]
+    fn unpaired_lines_run_on_to_the_word_circle() {
+        let word = "v".parse::<GallifreyanWord>().unwrap();
+        let stub = word.to_gallifreyan_characters()[0].modifier_shapes();
+
+        let lines = lines(&[(Vector2::new(0.0, 0.0), &word)]);
+
+        match (&lines[..], &stub[..]) {
+            (
+                [(_, start, end)],
+                [Shape::Segment {
+                    start: stub_start,
+                    end: stub_end,
+                }],
+            ) => {
+                assert!(start.distance(stub_start) < 1e-9);
+                assert!((end.rho() - word.size()).abs() < 1e-9);
+                assert!(((*end - *start).phi() - (*stub_end - *stub_start).phi()).abs() < 1e-9);
+            }
+            _ => panic!("Expected a single line."),
+        }
+    }
+
+    #[test]
+    fn lines_without_a_way_keep_their_stubs() {
+        let word = "gallifrey".parse::<GallifreyanWord>().unwrap();
+        let characters = word.to_gallifreyan_characters();
+        let index = characters
+            .iter()
+            .position(|character| character.to_letter() == Some(GallifreyanLetter::F))
+            .unwrap();
+        let stubs = characters[index]
+            .modifier_shapes()
+            .iter()
+            .filter_map(|shape| match *shape {
+                Shape::Segment { start, end } => Some((start, end)),
+                _ => None,
+            })
+            .collect::<Vec<(Vector2, Vector2)>>();
+        let lines = lines(&[(Vector2::new(0.0, 0.0), &word)]);
+        let kept = stubs
+            .iter()
+            .filter(|stub| {
+                lines.iter().any(|(_, start, end)| {
+                    start.distance(&stub.0) < 1e-9 && end.distance(&stub.1) < 1e-9
+                })
+            })
+            .count();
+
+        assert!(kept > 0);
+    }
+}
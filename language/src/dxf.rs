@@ -0,0 +1,204 @@
+use crate::layout::{Layout, Role};
+use crate::letters::*;
+use crate::numbers::*;
+use crate::sentences::*;
+use crate::shapes::Shape;
+use std::fmt::Write;
+
+/// The default layer and those of a layout, with the AutoCAD colour index they are drawn in.
+const LAYERS: [(&str, u8); 7] = [
+    (DEFAULT_LAYER, 7),
+    ("EDGES", 7),
+    ("BASES", 5),
+    ("MODIFIERS", 3),
+    ("VOWELS", 1),
+    ("SENTENCE", 7),
+    ("PUNCTUATION", 6),
+];
+/// The layer every shape drawn without a role is on.
+const DEFAULT_LAYER: &str = "0";
+
+/// Writes an ASCII DXF drawing of the word around its centre, with the word circle edges, bases,
+/// modifiers and vowels on layers of their own.
+pub fn word_to_dxf(word: &GallifreyanWord) -> String {
+    layout_to_dxf(&word.to_layout())
+}
+
+/// Writes an ASCII DXF drawing of the sentence around its centre, like `word_to_dxf`, with the
+/// sentence circle and punctuation on layers of their own.
+pub fn sentence_to_dxf(sentence: &GallifreyanSentence) -> String {
+    layout_to_dxf(&sentence.to_layout())
+}
+
+/// Writes an ASCII DXF drawing of the number around its centre, on the default layer.
+pub fn number_to_dxf(number: &GallifreyanNumber) -> String {
+    shapes_to_dxf(&number.to_shapes())
+}
+
+/// Writes an ASCII DXF drawing of the layout, putting every element on the layer of its role.
+pub fn layout_to_dxf(layout: &Layout) -> String {
+    let shapes = layout
+        .elements()
+        .iter()
+        .map(|element| (element.shape, layer(element.role)))
+        .collect::<Vec<(Shape, &str)>>();
+
+    dxf_document(&shapes)
+}
+
+/// Writes an ASCII DXF drawing of the shapes, all on the default layer.
+pub fn shapes_to_dxf(shapes: &[Shape]) -> String {
+    let shapes = shapes
+        .iter()
+        .map(|shape| (*shape, DEFAULT_LAYER))
+        .collect::<Vec<(Shape, &str)>>();
+
+    dxf_document(&shapes)
+}
+
+fn layer(role: Role) -> &'static str {
+    let index = match role {
+        Role::Edge => 1,
+        Role::Base => 2,
+        Role::Modifier => 3,
+        Role::Vowel => 4,
+        Role::SentenceCircle => 5,
+        Role::Punctuation(_) => 6,
+    };
+
+    LAYERS[index].0
+}
+
+/// Writes the shapes as native DXF entities: arcs as ARC, circles as CIRCLE, segments as LINE and
+/// dots as POINT, in the units of the glyphs.
+fn dxf_document(shapes: &[(Shape, &str)]) -> String {
+    let mut dxf = String::new();
+    let mut pair = |code: u16, value: &str| {
+        let _ = writeln!(dxf, "{code:>3}\n{value}");
+    };
+    let number = |value: f64| format!("{value:.6}");
+
+    pair(0, "SECTION");
+    pair(2, "HEADER");
+    pair(9, "$ACADVER");
+    pair(1, "AC1009");
+    pair(0, "ENDSEC");
+
+    pair(0, "SECTION");
+    pair(2, "TABLES");
+    pair(0, "TABLE");
+    pair(2, "LTYPE");
+    pair(70, "1");
+    pair(0, "LTYPE");
+    pair(2, "CONTINUOUS");
+    pair(70, "0");
+    pair(3, "Solid line");
+    pair(72, "65");
+    pair(73, "0");
+    pair(40, &number(0.0));
+    pair(0, "ENDTAB");
+    pair(0, "TABLE");
+    pair(2, "LAYER");
+    pair(70, &LAYERS.len().to_string());
+    LAYERS.iter().for_each(|(name, colour)| {
+        pair(0, "LAYER");
+        pair(2, name);
+        pair(70, "0");
+        pair(62, &colour.to_string());
+        pair(6, "CONTINUOUS");
+    });
+    pair(0, "ENDTAB");
+    pair(0, "ENDSEC");
+
+    pair(0, "SECTION");
+    pair(2, "ENTITIES");
+    shapes.iter().for_each(|(shape, layer)| match *shape {
+        Shape::Arc {
+            centre,
+            radius,
+            start,
+            end,
+        } => {
+            pair(0, "ARC");
+            pair(8, layer);
+            pair(10, &number(centre.x));
+            pair(20, &number(centre.y));
+            pair(30, &number(0.0));
+            pair(40, &number(radius));
+            pair(50, &number(start.to_degrees().rem_euclid(360.0)));
+            pair(51, &number(end.to_degrees().rem_euclid(360.0)));
+        }
+        Shape::Circle { centre, radius } => {
+            pair(0, "CIRCLE");
+            pair(8, layer);
+            pair(10, &number(centre.x));
+            pair(20, &number(centre.y));
+            pair(30, &number(0.0));
+            pair(40, &number(radius));
+        }
+        Shape::Dot { centre } => {
+            pair(0, "POINT");
+            pair(8, layer);
+            pair(10, &number(centre.x));
+            pair(20, &number(centre.y));
+            pair(30, &number(0.0));
+        }
+        Shape::Segment { start, end } => {
+            pair(0, "LINE");
+            pair(8, layer);
+            pair(10, &number(start.x));
+            pair(20, &number(start.y));
+            pair(30, &number(0.0));
+            pair(11, &number(end.x));
+            pair(21, &number(end.y));
+            pair(31, &number(0.0));
+        }
+    });
+    pair(0, "ENDSEC");
+    pair(0, "EOF");
+
+    dxf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The values of the group code `code` in the table or section after the pair `(2, name)`.
+    fn values<'a>(pairs: &[(&str, &'a str)], name: &str, code: &str) -> Vec<&'a str> {
+        pairs
+            .iter()
+            .skip_while(|pair| **pair != ("2", name))
+            .skip(1)
+            .take_while(|pair| !matches!(pair.1, "ENDTAB" | "ENDSEC"))
+            .filter(|pair| pair.0 == code)
+            .map(|pair| pair.1)
+            .collect()
+    }
+
+    #[test]
+    fn layers_and_line_types_are_declared() {
+        let sentence = "Doctor who?".parse::<GallifreyanSentence>().unwrap();
+        let number = "-1.5".parse::<GallifreyanNumber>().unwrap();
+
+        [sentence_to_dxf(&sentence), number_to_dxf(&number)]
+            .iter()
+            .for_each(|dxf| {
+                let lines = dxf.lines().map(str::trim).collect::<Vec<&str>>();
+                let pairs = lines
+                    .chunks_exact(2)
+                    .map(|pair| (pair[0], pair[1]))
+                    .collect::<Vec<(&str, &str)>>();
+                let layers = values(&pairs, "LAYER", "2");
+
+                assert_eq!(values(&pairs, "LTYPE", "2"), ["CONTINUOUS"]);
+                assert!(values(&pairs, "LAYER", "6")
+                    .iter()
+                    .all(|line_type| *line_type == "CONTINUOUS"));
+                assert!(values(&pairs, "ENTITIES", "8")
+                    .iter()
+                    .all(|layer| layers.contains(layer)));
+                assert_eq!(lines.last(), Some(&"EOF"));
+            });
+    }
+}
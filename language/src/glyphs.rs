@@ -1,49 +1,91 @@
+use crate::letters::GallifreyanLetter;
+#[cfg(feature = "serde")]
+use crate::shapes::point;
+use crate::shapes::{Shape, Tessellation};
 use geomath::{prelude::coordinates::Polar, vector::Vector2};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::f64::consts::{FRAC_PI_4, FRAC_PI_6, FRAC_PI_8, PI};
 
-const CRESCENT_BASE_RATIO: f64 = 0.9;
-const FULL_BASE_RATIO: f64 = 1.2;
-const MOON_BASE_RATIO: f64 = 1.0;
+const FRAC_PI_16: f64 = FRAC_PI_8 / 2.0;
+
 const DEFAULT_BASE_RATIO: f64 = 0.0;
-const DOT_OFFSET: f64 = 0.4;
-const CRESCENT_BASE_OFFSET: f64 = FRAC_PI_6;
-const QUARTER_BASE_OFFSET: f64 = 5.0 * PI / 9.0;
 
-pub fn draw_base(origin: Vector2, size: f64, range: (f64, f64), offset: f64) -> Vec<(f32, f32)> {
-    let start_range = ((range.0 * 180.0) / PI).round() as i64;
-    let end_range = ((range.1 * 180.0) / PI).round() as i64;
+/// The proportions of the glyphs. Ratios are relative to the size of the letter and angles are in
+/// radians.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Style {
+    /// The radius of a consonant.
+    pub letter_size: f64,
+    /// How far inside the word circle the centre of a Crescent base lies.
+    pub crescent_base_ratio: f64,
+    /// How far inside the word circle the centre of a Full base lies.
+    pub full_base_ratio: f64,
+    /// How far from its host the centre of an A or O vowel lies.
+    pub moon_base_ratio: f64,
+    /// The radius of the inner stroke of a doubled letter, relative to its outer stroke.
+    pub double_base_ratio: f64,
+    /// The radius of a vowel.
+    pub vowel_ratio: f64,
+    /// Half of the angle a Crescent base leaves open where it cuts the word circle.
+    pub crescent_base_offset: f64,
+    /// Half of the angle a Quarter base leaves open where it cuts the word circle.
+    pub quarter_base_offset: f64,
+    /// The distance between the dots and the stroke they sit beside.
+    pub dot_offset: f64,
+    /// How far the lines reach, relative to the radius of the stroke they leave.
+    pub line_length_factor: f64,
+    /// The angle between the middle of a letter and its outermost dots.
+    pub dot_spacing: f64,
+    /// The angle between the middle of a letter and its outermost lines.
+    pub line_spacing: f64,
+}
 
-    match start_range < end_range {
-        true => start_range..=end_range,
-        false => start_range..=end_range + 360,
+impl Default for Style {
+    fn default() -> Self {
+        Style {
+            letter_size: 2.0,
+            crescent_base_ratio: 0.9,
+            full_base_ratio: 1.2,
+            moon_base_ratio: 1.0,
+            double_base_ratio: 0.7,
+            vowel_ratio: 1.0 / 3.0,
+            crescent_base_offset: FRAC_PI_6,
+            quarter_base_offset: 5.0 * PI / 9.0,
+            dot_offset: 0.4,
+            line_length_factor: 1.5,
+            dot_spacing: FRAC_PI_4,
+            line_spacing: FRAC_PI_4,
+        }
     }
-    .map(|angle| origin + Vector2::from_polar(size, (angle as f64 * PI) / 180.0 + offset))
-    .map(|vector| (vector.x as f32, vector.y as f32))
-    .collect::<Vec<(f32, f32)>>()
 }
 
-fn draw_dots(origin: Vector2, size: f64, angles: Vec<f64>, offset: f64) -> Vec<Vec<(f32, f32)>> {
+fn dots(origin: Vector2, size: f64, angles: Vec<f64>, offset: f64, style: &Style) -> Vec<Shape> {
     angles
         .into_iter()
-        .map(|angle| origin - Vector2::from_polar(size + DOT_OFFSET, angle + offset))
-        .map(|vector| vec![(vector.x as f32, vector.y as f32)])
-        .collect::<Vec<Vec<(f32, f32)>>>()
+        .map(|angle| Shape::Dot {
+            centre: origin - Vector2::from_polar(size + style.dot_offset, angle + offset),
+        })
+        .collect::<Vec<Shape>>()
 }
 
-fn draw_lines(origin: Vector2, size: f64, angles: Vec<f64>, offset: f64) -> Vec<Vec<(f32, f32)>> {
+fn lines(origin: Vector2, size: f64, angles: Vec<f64>, offset: f64, style: &Style) -> Vec<Shape> {
     angles
         .into_iter()
-        .map(|angle| {
-            let point1 = origin - Vector2::from_polar(size, angle + offset);
-            let point2 = origin - Vector2::from_polar(1.5 * size, angle + offset);
-            vec![
-                (point1.x as f32, point1.y as f32),
-                (point2.x as f32, point2.y as f32),
-            ]
+        .map(|angle| Shape::Segment {
+            start: origin - Vector2::from_polar(size, angle + offset),
+            end: origin - Vector2::from_polar(style.line_length_factor * size, angle + offset),
         })
-        .collect::<Vec<Vec<(f32, f32)>>>()
+        .collect::<Vec<Shape>>()
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "type", content = "offset")
+)]
 pub enum Base {
     Moon(f64),
     Core,
@@ -54,59 +96,120 @@ pub enum Base {
 }
 
 impl Base {
-    pub fn base_vector(&self, letter_size: f64, phi: f64) -> Vector2 {
+    pub fn base_vector(&self, letter_size: f64, phi: f64, style: &Style) -> Vector2 {
         match self {
-            Base::Crescent => Vector2::from_polar(CRESCENT_BASE_RATIO * letter_size, phi),
-            Base::Full => Vector2::from_polar(FULL_BASE_RATIO * letter_size, phi),
-            Base::Moon(offset) => Vector2::from_polar(MOON_BASE_RATIO * letter_size, phi + offset),
+            Base::Crescent => Vector2::from_polar(style.crescent_base_ratio * letter_size, phi),
+            Base::Full => Vector2::from_polar(style.full_base_ratio * letter_size, phi),
+            Base::Moon(offset) => {
+                Vector2::from_polar(style.moon_base_ratio * letter_size, phi + offset)
+            }
             _ => Vector2::from_polar(DEFAULT_BASE_RATIO * letter_size, phi),
         }
     }
 
-    pub fn to_drawing(&self, origin: Vector2, letter_size: f64) -> Vec<(f32, f32)> {
+    fn stroke(
+        &self,
+        origin: Vector2,
+        letter_size: f64,
+        style: &Style,
+    ) -> (Vector2, f64, (f64, f64)) {
         match self {
-            Base::Moon(_) => draw_base(
-                origin + self.base_vector(letter_size, origin.phi()),
-                letter_size / 3.0,
+            Base::Moon(_) => (
+                origin + self.base_vector(letter_size, origin.phi(), style),
+                style.vowel_ratio * letter_size,
                 (0.0, 2.0 * PI),
-                origin.phi(),
             ),
-            Base::Core => draw_base(
-                origin - self.base_vector(letter_size, origin.phi()),
-                letter_size / 3.0,
+            Base::Core => (
+                origin - self.base_vector(letter_size, origin.phi(), style),
+                style.vowel_ratio * letter_size,
                 (0.0, 2.0 * PI),
-                origin.phi(),
             ),
-            Base::Crescent => draw_base(
-                origin - self.base_vector(letter_size, origin.phi()),
+            Base::Crescent => (
+                origin - self.base_vector(letter_size, origin.phi(), style),
                 letter_size,
-                (CRESCENT_BASE_OFFSET, -CRESCENT_BASE_OFFSET),
-                origin.phi(),
+                (style.crescent_base_offset, -style.crescent_base_offset),
             ),
-            Base::Full => draw_base(
-                origin - self.base_vector(letter_size, origin.phi()),
+            Base::Full | Base::New => (
+                origin - self.base_vector(letter_size, origin.phi(), style),
                 letter_size,
                 (0.0, 2.0 * PI),
-                origin.phi(),
             ),
-            Base::Quarter => draw_base(
-                origin - self.base_vector(letter_size, origin.phi()),
+            Base::Quarter => (
+                origin - self.base_vector(letter_size, origin.phi(), style),
                 letter_size,
-                (QUARTER_BASE_OFFSET, -QUARTER_BASE_OFFSET),
-                origin.phi(),
+                (style.quarter_base_offset, -style.quarter_base_offset),
             ),
-            Base::New => {
-                let base = Vector2::from_polar(DEFAULT_BASE_RATIO * letter_size, origin.phi());
-                draw_base(origin - base, letter_size, (0.0, 2.0 * PI), origin.phi())
-            }
         }
     }
 
+    /// The strokes of the base, adding a smaller concentric stroke when the letter is doubled.
+    /// The inner stroke of a doubled Crescent or Quarter base is cut where it meets the word
+    /// circle, if it does.
+    pub fn to_shapes(
+        &self,
+        origin: Vector2,
+        letter_size: f64,
+        doubled: bool,
+        style: &Style,
+    ) -> Vec<Shape> {
+        let (centre, radius, range) = self.stroke(origin, letter_size, style);
+        let mut strokes = vec![(radius, range)];
+
+        if doubled {
+            let inner_radius = style.double_base_ratio * radius;
+            let cut = (origin.rho().powi(2) - centre.rho().powi(2) - inner_radius.powi(2))
+                / (2.0 * centre.rho() * inner_radius);
+            let range = match self.has_edge() && cut.abs() <= 1.0 {
+                true => (cut.acos(), -cut.acos()),
+                false => (0.0, 2.0 * PI),
+            };
+
+            strokes.push((inner_radius, range));
+        }
+
+        strokes
+            .into_iter()
+            .map(|(radius, range)| {
+                Shape::arc(
+                    centre,
+                    radius,
+                    range.0 + origin.phi(),
+                    range.1 + origin.phi(),
+                )
+            })
+            .collect()
+    }
+
+    pub fn to_drawings(
+        &self,
+        origin: Vector2,
+        letter_size: f64,
+        doubled: bool,
+        style: &Style,
+    ) -> Vec<Vec<(f32, f32)>> {
+        self.to_shapes(origin, letter_size, doubled, style)
+            .iter()
+            .map(Shape::to_polyline)
+            .collect()
+    }
+
     pub fn has_edge(&self) -> bool {
         matches!(self, Base::Crescent | Base::Quarter)
     }
+
+    /// Whether a vowel with this base attaches to the word line rather than to the centre of its
+    /// consonant. Only A does, so that it stays outside the word circle whatever its consonant.
+    pub fn attaches_to_word_line(&self) -> bool {
+        matches!(self, Base::Moon(offset) if offset.cos() > 0.0)
+    }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "type", content = "offset")
+)]
 pub enum Modifier {
     Dot1,
     Dot2,
@@ -119,67 +222,213 @@ pub enum Modifier {
 }
 
 impl Modifier {
-    pub fn to_drawings(&self, origin: Vector2, letter_size: f64) -> Vec<Vec<(f32, f32)>> {
+    pub fn is_dot(&self) -> bool {
+        matches!(
+            self,
+            Modifier::Dot1 | Modifier::Dot2 | Modifier::Dot3 | Modifier::Dot4
+        )
+    }
+
+    pub fn to_drawings(
+        &self,
+        origin: Vector2,
+        letter_size: f64,
+        style: &Style,
+    ) -> Vec<Vec<(f32, f32)>> {
+        self.to_shapes(origin, letter_size, style)
+            .iter()
+            .map(Shape::to_polyline)
+            .collect()
+    }
+
+    pub fn to_shapes(&self, origin: Vector2, letter_size: f64, style: &Style) -> Vec<Shape> {
+        let (dot, line) = (style.dot_spacing, style.line_spacing);
+
         match self {
-            Modifier::Dot1 => draw_dots(origin, letter_size, vec![0.0], origin.phi()),
-            Modifier::Dot2 => draw_dots(
+            Modifier::Dot1 => dots(origin, letter_size, vec![0.0], origin.phi(), style),
+            Modifier::Dot2 => dots(origin, letter_size, vec![-dot, dot], origin.phi(), style),
+            Modifier::Dot3 => dots(
                 origin,
                 letter_size,
-                vec![-FRAC_PI_4, FRAC_PI_4],
+                vec![-dot, 0.0, dot],
                 origin.phi(),
+                style,
             ),
-            Modifier::Dot3 => draw_dots(
+            Modifier::Dot4 => dots(
                 origin,
                 letter_size,
-                vec![-FRAC_PI_4, 0.0, FRAC_PI_4],
+                vec![-dot, -dot / 2.0, dot / 2.0, dot],
                 origin.phi(),
+                style,
             ),
-            Modifier::Dot4 => draw_dots(
+            Modifier::VowelLine1(offset) => lines(
+                origin,
+                style.vowel_ratio * letter_size,
+                vec![0.0],
+                origin.phi() + offset,
+                style,
+            ),
+            Modifier::Line1 => lines(origin, letter_size, vec![0.0], origin.phi(), style),
+            Modifier::Line2 => lines(origin, letter_size, vec![-line, line], origin.phi(), style),
+            Modifier::Line3 => lines(
                 origin,
                 letter_size,
-                vec![-FRAC_PI_4, -FRAC_PI_8, FRAC_PI_8, FRAC_PI_4],
+                vec![-line, 0.0, line],
                 origin.phi(),
+                style,
             ),
-            Modifier::VowelLine1(offset) => {
-                draw_lines(origin, letter_size / 3.0, vec![0.0], origin.phi() + offset)
-            }
-            Modifier::Line1 => draw_lines(origin, letter_size, vec![0.0], origin.phi()),
-            Modifier::Line2 => draw_lines(
-                origin,
+        }
+    }
+}
+
+/// The punctuation marks drawn on the sentence circle between words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Punctuation {
+    Period,
+    Comma,
+    Semicolon,
+    Colon,
+    Question,
+    Exclamation,
+    Quote,
+    Dash,
+}
+
+impl Punctuation {
+    pub fn to_drawings(
+        &self,
+        origin: Vector2,
+        letter_size: f64,
+        style: &Style,
+    ) -> Vec<Vec<(f32, f32)>> {
+        self.to_shapes(origin, letter_size, style)
+            .iter()
+            .map(Shape::to_polyline)
+            .collect()
+    }
+
+    /// The shapes of the mark at `origin` on the sentence circle, pointing the dots and lines
+    /// inwards.
+    pub fn to_shapes(&self, origin: Vector2, letter_size: f64, style: &Style) -> Vec<Shape> {
+        let anchor = origin + Vector2::from_polar(letter_size, origin.phi());
+
+        match self {
+            Punctuation::Period => vec![Shape::Circle {
+                centre: origin,
+                radius: style.vowel_ratio * letter_size,
+            }],
+            Punctuation::Comma => vec![Shape::Dot { centre: origin }],
+            Punctuation::Semicolon => dots(anchor, letter_size, vec![0.0], origin.phi(), style),
+            Punctuation::Colon => vec![
+                Shape::Circle {
+                    centre: origin,
+                    radius: style.vowel_ratio * letter_size,
+                },
+                Shape::Circle {
+                    centre: origin,
+                    radius: letter_size / 5.0,
+                },
+            ],
+            Punctuation::Question => dots(
+                anchor,
                 letter_size,
-                vec![-FRAC_PI_4, FRAC_PI_4],
+                vec![-FRAC_PI_16, FRAC_PI_16],
                 origin.phi(),
+                style,
             ),
-            Modifier::Line3 => draw_lines(
-                origin,
+            Punctuation::Exclamation => dots(
+                anchor,
+                letter_size,
+                vec![-FRAC_PI_8, 0.0, FRAC_PI_8],
+                origin.phi(),
+                style,
+            ),
+            Punctuation::Quote => lines(anchor, letter_size, vec![0.0], origin.phi(), style),
+            Punctuation::Dash => lines(
+                anchor,
                 letter_size,
-                vec![-FRAC_PI_4, 0.0, FRAC_PI_4],
+                vec![-FRAC_PI_16, 0.0, FRAC_PI_16],
                 origin.phi(),
+                style,
             ),
         }
     }
 }
 
+/// A character of a word, drawn at `origin` in word coordinates.
+///
+/// With the `serde` feature, the style is left out, as it is that of the word, and read back as
+/// the default style.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GallifreyanCharacter {
     pub base: Base,
     pub modifier: Option<Modifier>,
+    #[cfg_attr(feature = "serde", serde(with = "point"))]
     pub origin: Vector2,
     pub size: f64,
+    pub doubled: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub style: Style,
 }
 
 impl GallifreyanCharacter {
     pub fn base_vector(&self) -> Vector2 {
-        self.base.base_vector(self.size, self.origin.phi())
+        self.base
+            .base_vector(self.size, self.origin.phi(), &self.style)
+    }
+
+    pub fn base_shapes(&self) -> Vec<Shape> {
+        self.base
+            .to_shapes(self.origin, self.size, self.doubled, &self.style)
+    }
+
+    pub fn modifier_shapes(&self) -> Vec<Shape> {
+        self.modifier.as_ref().map_or(Vec::new(), |modifier| {
+            modifier.to_shapes(self.origin - self.base_vector(), self.size, &self.style)
+        })
+    }
+
+    /// The shapes of the base followed by the shapes of the modifier.
+    pub fn to_shapes(&self) -> Vec<Shape> {
+        let mut shapes = self.base_shapes();
+        shapes.append(&mut self.modifier_shapes());
+        shapes
+    }
+
+    pub fn draw_base(&self) -> Vec<Vec<(f32, f32)>> {
+        self.base
+            .to_drawings(self.origin, self.size, self.doubled, &self.style)
     }
 
-    pub fn draw_base(&self) -> Vec<(f32, f32)> {
-        self.base.to_drawing(self.origin, self.size)
+    /// Draws the base like [`draw_base`](Self::draw_base), tessellated with `tessellation`.
+    pub fn draw_base_with(&self, tessellation: Tessellation) -> Vec<Vec<(f32, f32)>> {
+        self.base_shapes()
+            .iter()
+            .map(|shape| shape.tessellate(tessellation))
+            .collect()
     }
 
     pub fn draw_modifier(&self) -> Option<Vec<Vec<(f32, f32)>>> {
-        self.modifier
-            .as_ref()
-            .map(|modifier| modifier.to_drawings(self.origin - self.base_vector(), self.size))
+        self.modifier.as_ref().map(|modifier| {
+            modifier.to_drawings(self.origin - self.base_vector(), self.size, &self.style)
+        })
+    }
+
+    /// Draws the modifier like [`draw_modifier`](Self::draw_modifier), tessellated with
+    /// `tessellation`.
+    pub fn draw_modifier_with(&self, tessellation: Tessellation) -> Option<Vec<Vec<(f32, f32)>>> {
+        self.modifier.as_ref().map(|_| {
+            self.modifier_shapes()
+                .iter()
+                .map(|shape| shape.tessellate(tessellation))
+                .collect()
+        })
+    }
+
+    /// The letter this character is drawn for, if any.
+    pub fn to_letter(&self) -> Option<GallifreyanLetter> {
+        GallifreyanLetter::from_glyph(&self.base, self.modifier.as_ref())
     }
 
     pub fn has_edge(&self) -> bool {
@@ -193,9 +442,11 @@ impl GallifreyanCharacter {
     pub fn starting_angle(&self) -> Option<f64> {
         match self.base {
             Base::Crescent => {
-                Some(self.origin.phi() - self.find_edge_wrt_word(CRESCENT_BASE_OFFSET))
+                Some(self.origin.phi() - self.find_edge_wrt_word(self.style.crescent_base_offset))
+            }
+            Base::Quarter => {
+                Some(self.origin.phi() - self.find_edge_wrt_word(self.style.quarter_base_offset))
             }
-            Base::Quarter => Some(self.origin.phi() - self.find_edge_wrt_word(QUARTER_BASE_OFFSET)),
             _ => None,
         }
     }
@@ -203,10 +454,89 @@ impl GallifreyanCharacter {
     pub fn ending_angle(&self) -> Option<f64> {
         match self.base {
             Base::Crescent => {
-                Some(self.origin.phi() + self.find_edge_wrt_word(CRESCENT_BASE_OFFSET))
+                Some(self.origin.phi() + self.find_edge_wrt_word(self.style.crescent_base_offset))
+            }
+            Base::Quarter => {
+                Some(self.origin.phi() + self.find_edge_wrt_word(self.style.quarter_base_offset))
             }
-            Base::Quarter => Some(self.origin.phi() + self.find_edge_wrt_word(QUARTER_BASE_OFFSET)),
             _ => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Role;
+    use crate::sentences::GallifreyanSentence;
+
+    #[test]
+    fn punctuation_marks_point_into_the_sentence_circle() {
+        let style = Style::default();
+        let origin = Vector2::from_polar(10.0, 1.0);
+        let inside = |point: Vector2| point.rho() <= origin.rho() + 1e-9;
+
+        [
+            (Punctuation::Period, (1, 0, 0)),
+            (Punctuation::Comma, (0, 1, 0)),
+            (Punctuation::Semicolon, (0, 1, 0)),
+            (Punctuation::Colon, (2, 0, 0)),
+            (Punctuation::Question, (0, 2, 0)),
+            (Punctuation::Exclamation, (0, 3, 0)),
+            (Punctuation::Quote, (0, 0, 1)),
+            (Punctuation::Dash, (0, 0, 3)),
+        ]
+        .iter()
+        .for_each(|(mark, (circles, dots, segments))| {
+            let shapes = mark.to_shapes(origin, style.letter_size, &style);
+            let count =
+                |kind: fn(&Shape) -> bool| shapes.iter().filter(|shape| kind(shape)).count();
+
+            assert_eq!(
+                count(|shape| matches!(shape, Shape::Circle { .. })),
+                *circles
+            );
+            assert_eq!(count(|shape| matches!(shape, Shape::Dot { .. })), *dots);
+            assert_eq!(
+                count(|shape| matches!(shape, Shape::Segment { .. })),
+                *segments
+            );
+            shapes.iter().for_each(|shape| match *shape {
+                Shape::Circle { centre, .. } => assert!((centre - origin).rho() < 1e-9),
+                Shape::Dot { centre } => assert!(inside(centre), "{mark:?}"),
+                Shape::Segment { start, end } => {
+                    assert!(inside(end) && end.rho() < start.rho(), "{mark:?}")
+                }
+                Shape::Arc { .. } => panic!("{mark:?} should not have arcs."),
+            });
+        });
+    }
+
+    #[test]
+    fn punctuation_is_drawn_in_the_gaps_between_words() {
+        let sentence = "hello, world!".parse::<GallifreyanSentence>().unwrap();
+        let marks = sentence
+            .to_layout()
+            .elements()
+            .iter()
+            .filter_map(|element| match element.role {
+                Role::Punctuation(mark) => Some(mark),
+                _ => None,
+            })
+            .collect::<Vec<Punctuation>>();
+
+        assert_eq!(
+            sentence.punctuation(),
+            [(1, Punctuation::Comma), (2, Punctuation::Exclamation)]
+        );
+        assert_eq!(
+            marks,
+            [
+                Punctuation::Comma,
+                Punctuation::Exclamation,
+                Punctuation::Exclamation,
+                Punctuation::Exclamation
+            ]
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use crate::glyphs::{Base, GallifreyanCharacter, Modifier, Punctuation};
+use crate::letters::GallifreyanLetter;
+use crate::shapes::{BoundingBox, Shape};
+use geomath::vector::Vector2;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The part of a drawing a shape belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(tag = "type", content = "mark")
+)]
+pub enum Role {
+    /// The circle or arc of a consonant.
+    Base,
+    /// The dots or lines of a consonant.
+    Modifier,
+    /// A vowel, along with its line.
+    Vowel,
+    /// An arc of a word circle between the letters cutting into it.
+    Edge,
+    /// The circle around the words of a sentence.
+    SentenceCircle,
+    Punctuation(Punctuation),
+}
+
+/// A shape of a layout along with what it draws.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Element {
+    pub shape: Shape,
+    pub role: Role,
+    /// The index of the word in its sentence. A word laid out on its own is word 0, and the
+    /// sentence circle and punctuation belong to no word.
+    pub word: Option<usize>,
+    /// The letter drawn, for bases, modifiers and vowels.
+    pub letter: Option<GallifreyanLetter>,
+    /// The index of the letter in its word. A doubled letter has the index of its first letter.
+    pub index: Option<usize>,
+    /// The byte offset of the letter in the text the word or sentence was parsed from.
+    pub byte_offset: Option<usize>,
+    /// The `char` offset of the letter in the text the word or sentence was parsed from.
+    pub char_offset: Option<usize>,
+    /// The base of the character drawing the letter.
+    pub base: Option<Base>,
+    /// The modifier of the character drawing the letter, if it has one.
+    pub modifier: Option<Modifier>,
+    pub bounds: BoundingBox,
+}
+
+impl Element {
+    pub(crate) fn new(shape: Shape, role: Role) -> Element {
+        Element {
+            shape,
+            role,
+            word: None,
+            letter: None,
+            index: None,
+            byte_offset: None,
+            char_offset: None,
+            base: None,
+            modifier: None,
+            bounds: shape.bounds(),
+        }
+    }
+
+    pub(crate) fn with_letter(
+        self,
+        index: usize,
+        (byte_offset, char_offset): (usize, usize),
+        letter: GallifreyanLetter,
+        character: &GallifreyanCharacter,
+    ) -> Element {
+        Element {
+            letter: Some(letter),
+            index: Some(index),
+            byte_offset: Some(byte_offset),
+            char_offset: Some(char_offset),
+            base: Some(character.base),
+            modifier: character.modifier,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_word(self, word: usize) -> Element {
+        Element {
+            word: Some(word),
+            ..self
+        }
+    }
+
+    pub fn translate(&self, offset: Vector2) -> Element {
+        Element {
+            shape: self.shape.translate(offset),
+            bounds: BoundingBox {
+                min: self.bounds.min + offset,
+                max: self.bounds.max + offset,
+            },
+            ..*self
+        }
+    }
+}
+
+/// The shapes of a word or sentence, each grouped with the letter and part of the drawing it
+/// comes from, in drawing order.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Layout {
+    elements: Vec<Element>,
+}
+
+impl Layout {
+    pub(crate) fn new(elements: Vec<Element>) -> Layout {
+        Layout { elements }
+    }
+
+    pub fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+
+    pub fn to_shapes(&self) -> Vec<Shape> {
+        self.elements.iter().map(|element| element.shape).collect()
+    }
+
+    /// The smallest box holding every shape, if there are any.
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        self.elements
+            .iter()
+            .map(|element| element.bounds)
+            .reduce(|bounds, other| bounds.union(&other))
+    }
+
+    /// The elements drawing the letter at `index` of the word at `word`.
+    pub fn letter_elements(&self, word: usize, index: usize) -> impl Iterator<Item = &Element> {
+        self.elements
+            .iter()
+            .filter(move |element| element.word == Some(word) && element.index == Some(index))
+    }
+
+    /// The elements whose bounds hold `point`, topmost first.
+    pub fn elements_at(&self, point: Vector2) -> impl Iterator<Item = &Element> {
+        self.elements
+            .iter()
+            .rev()
+            .filter(move |element| element.bounds.contains(point))
+    }
+
+    pub fn translate(&self, offset: Vector2) -> Layout {
+        Layout {
+            elements: self
+                .elements
+                .iter()
+                .map(|element| element.translate(offset))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::letters::GallifreyanWord;
+    use crate::sentences::GallifreyanSentence;
+
+    /// The letter index and the byte and `char` offsets of the first element drawing `letter`
+    /// in the word at `word`.
+    fn letter_position(
+        layout: &Layout,
+        word: usize,
+        letter: GallifreyanLetter,
+    ) -> (Option<usize>, Option<usize>, Option<usize>) {
+        layout
+            .elements()
+            .iter()
+            .find(|element| element.word == Some(word) && element.letter == Some(letter))
+            .map(|element| (element.index, element.byte_offset, element.char_offset))
+            .unwrap()
+    }
+
+    #[test]
+    fn letters_keep_their_offsets_after_a_digraph() {
+        let layout = "chat".parse::<GallifreyanWord>().unwrap().to_layout();
+
+        assert_eq!(
+            letter_position(&layout, 0, GallifreyanLetter::CH),
+            (Some(0), Some(0), Some(0))
+        );
+        assert_eq!(
+            letter_position(&layout, 0, GallifreyanLetter::A),
+            (Some(1), Some(2), Some(2))
+        );
+        assert_eq!(
+            letter_position(&layout, 0, GallifreyanLetter::T),
+            (Some(2), Some(3), Some(3))
+        );
+    }
+
+    #[test]
+    fn letters_of_a_sentence_are_counted_from_its_start() {
+        let sentence = "\u{201C}hi\u{201D} chat"
+            .parse::<GallifreyanSentence>()
+            .unwrap();
+        let layout = sentence.with_stacking(true).to_layout();
+
+        assert_eq!(
+            letter_position(&layout, 0, GallifreyanLetter::I),
+            (Some(1), Some(4), Some(2))
+        );
+        assert_eq!(
+            letter_position(&layout, 1, GallifreyanLetter::A),
+            (Some(1), Some(11), Some(7))
+        );
+    }
+}
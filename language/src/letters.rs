@@ -1,16 +1,100 @@
+use crate::connections::connected_modifier_shapes;
 use crate::glyphs::*;
+use crate::layout::{Element, Layout, Role};
+use crate::shapes::{self, Shape, Tessellation};
 use core::fmt;
 use geomath::prelude::coordinates::Polar;
 use geomath::vector::Vector2;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::error::Error;
 use std::f64::consts::{FRAC_PI_2, PI};
 use std::fmt::Display;
+use std::iter;
 use std::str::FromStr;
+use unicode_segmentation::UnicodeSegmentation;
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct ParseGallifreyanLetterError;
+/// How close, in letter sizes, the strokes of different groups of letters can come.
+const MIN_CLEARANCE: f64 = 0.25;
+/// How much smaller the letters are made each time the groups do not fit around the word circle.
+const SHRINK_FACTOR: f64 = 0.9;
+/// The smallest letters are drawn at this part of the letter size of the style.
+const MIN_LETTER_SCALE: f64 = 0.3;
+/// The number of halvings narrowing down the angle between two groups of letters.
+const SPACING_STEPS: usize = 20;
+/// The distance, in letter sizes, between the points of a stroke checked for clearance.
+const CLEARANCE_STEP: f64 = 0.1;
+/// The size of each consonant of a stack relative to the one around it.
+const STACK_RATIO: f64 = 0.6;
+/// The largest number of consonants stacked in one position.
+const MAX_STACK: usize = 3;
+
+/// The error returned when text contains a character that has no Gallifreyan letter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseGallifreyanLetterError {
+    character: char,
+    byte_offset: usize,
+    char_offset: usize,
+    grapheme: String,
+}
+
+impl ParseGallifreyanLetterError {
+    pub(crate) fn new(
+        text: &str,
+        byte_offset: usize,
+        char_offset: usize,
+    ) -> ParseGallifreyanLetterError {
+        let character = text[byte_offset..].chars().next().unwrap_or_default();
+        let grapheme = text
+            .grapheme_indices(true)
+            .find(|(start, grapheme)| (*start..start + grapheme.len()).contains(&byte_offset))
+            .map(|(_, grapheme)| grapheme.to_string())
+            .unwrap_or_else(|| character.to_string());
+
+        ParseGallifreyanLetterError {
+            character,
+            byte_offset,
+            char_offset,
+            grapheme,
+        }
+    }
+
+    /// The character that could not be parsed.
+    pub fn character(&self) -> char {
+        self.character
+    }
+
+    /// The byte offset of the character in the parsed text.
+    pub fn byte_offset(&self) -> usize {
+        self.byte_offset
+    }
+
+    /// The `char` offset of the character in the parsed text.
+    pub fn char_offset(&self) -> usize {
+        self.char_offset
+    }
+
+    /// The grapheme cluster the character belongs to.
+    pub fn grapheme(&self) -> &str {
+        &self.grapheme
+    }
+}
+
+impl Display for ParseGallifreyanLetterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} in {:?} at character {} (byte {}) has no Gallifreyan letter",
+            self.character, self.grapheme, self.char_offset, self.byte_offset
+        )
+    }
+}
+
+impl Error for ParseGallifreyanLetterError {}
 
 /// An enumeration for the letters in the Gallifreyan alphabet.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GallifreyanLetter {
     A,
     E,
@@ -128,222 +212,398 @@ impl FromStr for GallifreyanLetter {
             "QU" => Result::Ok(GallifreyanLetter::QU),
             "X" => Result::Ok(GallifreyanLetter::X),
             "NG" => Result::Ok(GallifreyanLetter::NG),
-            _ => Result::Err(ParseGallifreyanLetterError),
+            _ => Result::Err(ParseGallifreyanLetterError::new(s, 0, 0)),
+        }
+    }
+}
+
+impl FromStr for Punctuation {
+    type Err = ParseGallifreyanLetterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "." => Result::Ok(Punctuation::Period),
+            "," => Result::Ok(Punctuation::Comma),
+            ";" => Result::Ok(Punctuation::Semicolon),
+            ":" => Result::Ok(Punctuation::Colon),
+            "?" => Result::Ok(Punctuation::Question),
+            "!" => Result::Ok(Punctuation::Exclamation),
+            "\"" | "\u{201C}" | "\u{201D}" => Result::Ok(Punctuation::Quote),
+            "-" | "\u{2013}" | "\u{2014}" => Result::Ok(Punctuation::Dash),
+            _ => Result::Err(ParseGallifreyanLetterError::new(s, 0, 0)),
         }
     }
 }
 
 impl GallifreyanLetter {
-    pub fn to_gallifreyan_character(&self, origin: Vector2, size: f64) -> GallifreyanCharacter {
+    pub fn to_gallifreyan_character(
+        &self,
+        origin: Vector2,
+        size: f64,
+        style: &Style,
+    ) -> GallifreyanCharacter {
         match self {
             GallifreyanLetter::A => GallifreyanCharacter {
                 base: Base::Moon(0.0),
                 modifier: None,
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::E => GallifreyanCharacter {
                 base: Base::Core,
                 modifier: None,
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::I => GallifreyanCharacter {
                 base: Base::Core,
                 modifier: Some(Modifier::VowelLine1(0.0)),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::O => GallifreyanCharacter {
                 base: Base::Moon(PI),
                 modifier: None,
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::U => GallifreyanCharacter {
                 base: Base::Core,
                 modifier: Some(Modifier::VowelLine1(PI)),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::B => GallifreyanCharacter {
                 base: Base::Crescent,
                 modifier: None,
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::CH => GallifreyanCharacter {
                 base: Base::Crescent,
                 modifier: Some(Modifier::Dot2),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::D => GallifreyanCharacter {
                 base: Base::Crescent,
                 modifier: Some(Modifier::Dot3),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::G => GallifreyanCharacter {
                 base: Base::Crescent,
                 modifier: Some(Modifier::Line1),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::H => GallifreyanCharacter {
                 base: Base::Crescent,
                 modifier: Some(Modifier::Line2),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::F => GallifreyanCharacter {
                 base: Base::Crescent,
                 modifier: Some(Modifier::Line3),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::J => GallifreyanCharacter {
                 base: Base::Full,
                 modifier: None,
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::PH => GallifreyanCharacter {
                 base: Base::Full,
                 modifier: Some(Modifier::Dot1),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::K => GallifreyanCharacter {
                 base: Base::Full,
                 modifier: Some(Modifier::Dot2),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::L => GallifreyanCharacter {
                 base: Base::Full,
                 modifier: Some(Modifier::Dot3),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::C => GallifreyanCharacter {
                 base: Base::Full,
                 modifier: Some(Modifier::Dot4),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::N => GallifreyanCharacter {
                 base: Base::Full,
                 modifier: Some(Modifier::Line1),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::P => GallifreyanCharacter {
                 base: Base::Full,
                 modifier: Some(Modifier::Line2),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::M => GallifreyanCharacter {
                 base: Base::Full,
                 modifier: Some(Modifier::Line3),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::T => GallifreyanCharacter {
                 base: Base::Quarter,
                 modifier: None,
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::WH => GallifreyanCharacter {
                 base: Base::Quarter,
                 modifier: Some(Modifier::Dot1),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::SH => GallifreyanCharacter {
                 base: Base::Quarter,
                 modifier: Some(Modifier::Dot2),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::R => GallifreyanCharacter {
                 base: Base::Quarter,
                 modifier: Some(Modifier::Dot3),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::V => GallifreyanCharacter {
                 base: Base::Quarter,
                 modifier: Some(Modifier::Line1),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::W => GallifreyanCharacter {
                 base: Base::Quarter,
                 modifier: Some(Modifier::Line2),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::S => GallifreyanCharacter {
                 base: Base::Quarter,
                 modifier: Some(Modifier::Line3),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::TH => GallifreyanCharacter {
                 base: Base::New,
                 modifier: None,
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::GH => GallifreyanCharacter {
                 base: Base::New,
                 modifier: Some(Modifier::Dot1),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::Y => GallifreyanCharacter {
                 base: Base::New,
                 modifier: Some(Modifier::Dot2),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::Z => GallifreyanCharacter {
                 base: Base::New,
                 modifier: Some(Modifier::Dot3),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::Q => GallifreyanCharacter {
                 base: Base::New,
                 modifier: Some(Modifier::Dot4),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::QU => GallifreyanCharacter {
                 base: Base::New,
                 modifier: Some(Modifier::Line1),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::X => GallifreyanCharacter {
                 base: Base::New,
                 modifier: Some(Modifier::Line2),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
             GallifreyanLetter::NG => GallifreyanCharacter {
                 base: Base::New,
                 modifier: Some(Modifier::Line3),
                 origin,
                 size,
+                doubled: false,
+                style: *style,
             },
         }
     }
 
-    fn is_vowel(&self) -> bool {
+    /// Decodes the letter drawn with `base` and `modifier`, if any letter is drawn that way.
+    pub fn from_glyph(base: &Base, modifier: Option<&Modifier>) -> Option<GallifreyanLetter> {
+        let facing_out = |offset: &f64| offset.cos() > 0.0;
+
+        match (base, modifier) {
+            (Base::Moon(offset), None) if facing_out(offset) => Some(GallifreyanLetter::A),
+            (Base::Moon(_), None) => Some(GallifreyanLetter::O),
+            (Base::Core, None) => Some(GallifreyanLetter::E),
+            (Base::Core, Some(Modifier::VowelLine1(offset))) if facing_out(offset) => {
+                Some(GallifreyanLetter::I)
+            }
+            (Base::Core, Some(Modifier::VowelLine1(_))) => Some(GallifreyanLetter::U),
+            (Base::Crescent, None) => Some(GallifreyanLetter::B),
+            (Base::Crescent, Some(Modifier::Dot2)) => Some(GallifreyanLetter::CH),
+            (Base::Crescent, Some(Modifier::Dot3)) => Some(GallifreyanLetter::D),
+            (Base::Crescent, Some(Modifier::Line1)) => Some(GallifreyanLetter::G),
+            (Base::Crescent, Some(Modifier::Line2)) => Some(GallifreyanLetter::H),
+            (Base::Crescent, Some(Modifier::Line3)) => Some(GallifreyanLetter::F),
+            (Base::Full, None) => Some(GallifreyanLetter::J),
+            (Base::Full, Some(Modifier::Dot1)) => Some(GallifreyanLetter::PH),
+            (Base::Full, Some(Modifier::Dot2)) => Some(GallifreyanLetter::K),
+            (Base::Full, Some(Modifier::Dot3)) => Some(GallifreyanLetter::L),
+            (Base::Full, Some(Modifier::Dot4)) => Some(GallifreyanLetter::C),
+            (Base::Full, Some(Modifier::Line1)) => Some(GallifreyanLetter::N),
+            (Base::Full, Some(Modifier::Line2)) => Some(GallifreyanLetter::P),
+            (Base::Full, Some(Modifier::Line3)) => Some(GallifreyanLetter::M),
+            (Base::Quarter, None) => Some(GallifreyanLetter::T),
+            (Base::Quarter, Some(Modifier::Dot1)) => Some(GallifreyanLetter::WH),
+            (Base::Quarter, Some(Modifier::Dot2)) => Some(GallifreyanLetter::SH),
+            (Base::Quarter, Some(Modifier::Dot3)) => Some(GallifreyanLetter::R),
+            (Base::Quarter, Some(Modifier::Line1)) => Some(GallifreyanLetter::V),
+            (Base::Quarter, Some(Modifier::Line2)) => Some(GallifreyanLetter::W),
+            (Base::Quarter, Some(Modifier::Line3)) => Some(GallifreyanLetter::S),
+            (Base::New, None) => Some(GallifreyanLetter::TH),
+            (Base::New, Some(Modifier::Dot1)) => Some(GallifreyanLetter::GH),
+            (Base::New, Some(Modifier::Dot2)) => Some(GallifreyanLetter::Y),
+            (Base::New, Some(Modifier::Dot3)) => Some(GallifreyanLetter::Z),
+            (Base::New, Some(Modifier::Dot4)) => Some(GallifreyanLetter::Q),
+            (Base::New, Some(Modifier::Line1)) => Some(GallifreyanLetter::QU),
+            (Base::New, Some(Modifier::Line2)) => Some(GallifreyanLetter::X),
+            (Base::New, Some(Modifier::Line3)) => Some(GallifreyanLetter::NG),
+            _ => None,
+        }
+    }
+
+    /// The Latin transliteration of the letter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GallifreyanLetter::A => "A",
+            GallifreyanLetter::E => "E",
+            GallifreyanLetter::I => "I",
+            GallifreyanLetter::O => "O",
+            GallifreyanLetter::U => "U",
+            GallifreyanLetter::B => "B",
+            GallifreyanLetter::CH => "CH",
+            GallifreyanLetter::D => "D",
+            GallifreyanLetter::G => "G",
+            GallifreyanLetter::H => "H",
+            GallifreyanLetter::F => "F",
+            GallifreyanLetter::J => "J",
+            GallifreyanLetter::PH => "PH",
+            GallifreyanLetter::K => "K",
+            GallifreyanLetter::L => "L",
+            GallifreyanLetter::C => "C",
+            GallifreyanLetter::N => "N",
+            GallifreyanLetter::P => "P",
+            GallifreyanLetter::M => "M",
+            GallifreyanLetter::T => "T",
+            GallifreyanLetter::WH => "WH",
+            GallifreyanLetter::SH => "SH",
+            GallifreyanLetter::R => "R",
+            GallifreyanLetter::V => "V",
+            GallifreyanLetter::W => "W",
+            GallifreyanLetter::S => "S",
+            GallifreyanLetter::TH => "TH",
+            GallifreyanLetter::GH => "GH",
+            GallifreyanLetter::Y => "Y",
+            GallifreyanLetter::Z => "Z",
+            GallifreyanLetter::Q => "Q",
+            GallifreyanLetter::QU => "QU",
+            GallifreyanLetter::X => "X",
+            GallifreyanLetter::NG => "NG",
+        }
+    }
+
+    pub(crate) fn is_vowel(&self) -> bool {
         matches!(
             self,
             GallifreyanLetter::A
@@ -355,139 +615,721 @@ impl GallifreyanLetter {
     }
 }
 
+/// A word written around its word circle.
+///
+/// With the `serde` feature, a word is stored laid out as a `WordLayout`, and read back from the
+/// letters and style of one.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(into = "WordLayout", from = "WordLayout")
+)]
 pub struct GallifreyanWord {
     letters: Vec<GallifreyanLetter>,
+    offsets: Vec<(usize, usize)>,
     size: f64,
+    style: Style,
+    stacking: bool,
+    letter_size: f64,
+    positions: Vec<f64>,
 }
 
-impl GallifreyanWord {
-    const LETTER_SIZE: f64 = 2.0;
+/// The serialized form of a word: its letters and style, and the characters and word circle
+/// edges they are drawn as, in word coordinates around the centre of the word circle. The style is
+/// stored once, and given back to every character when read.
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(from = "StoredWordLayout")]
+pub struct WordLayout {
+    /// The letters of the word, in reading order.
+    pub letters: Vec<GallifreyanLetter>,
+    /// The byte and `char` offsets of each letter in the text the word was parsed from.
+    #[serde(default)]
+    pub offsets: Vec<(usize, usize)>,
+    /// The radius of the word circle.
+    pub size: f64,
+    pub style: Style,
+    /// Whether consecutive consonants of the same base are stacked in one position.
+    #[serde(default)]
+    pub stacking: bool,
+    /// The characters drawn for the letters, with their bases, modifiers, origins and sizes.
+    pub characters: Vec<GallifreyanCharacter>,
+    /// The arcs of the word circle between the characters cutting into it.
+    pub edges: Vec<Shape>,
+}
 
-    pub fn from(word: &str) -> GallifreyanWord {
-        let mut grouped_letters = Vec::<String>::new();
-        let mut char_iter = word.chars().into_iter().peekable();
+/// A `WordLayout` as read, with characters that do not have the style of the word yet.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct StoredWordLayout {
+    letters: Vec<GallifreyanLetter>,
+    #[serde(default)]
+    offsets: Vec<(usize, usize)>,
+    size: f64,
+    style: Style,
+    #[serde(default)]
+    stacking: bool,
+    characters: Vec<GallifreyanCharacter>,
+    edges: Vec<Shape>,
+}
 
-        while let Some(current_letter) = char_iter.next() {
-            let entry = match current_letter.to_ascii_uppercase() {
-                'C' | 'P' | 'W' | 'S' | 'T' | 'G' => match char_iter.next_if_eq(&'H') {
-                    Some(next_letter) => current_letter.to_string() + &next_letter.to_string(),
-                    None => current_letter.to_string(),
-                },
-                'Q' => match char_iter.next_if_eq(&'U') {
-                    Some(next_letter) => current_letter.to_string() + &next_letter.to_string(),
-                    None => current_letter.to_string(),
-                },
-                'N' => match char_iter.next_if_eq(&'G') {
-                    Some(next_letter) => current_letter.to_string() + &next_letter.to_string(),
-                    None => current_letter.to_string(),
-                },
-                _ => current_letter.to_string(),
-            };
+#[cfg(feature = "serde")]
+impl From<StoredWordLayout> for WordLayout {
+    fn from(stored: StoredWordLayout) -> Self {
+        WordLayout {
+            characters: stored
+                .characters
+                .into_iter()
+                .map(|character| GallifreyanCharacter {
+                    style: stored.style,
+                    ..character
+                })
+                .collect(),
+            letters: stored.letters,
+            offsets: stored.offsets,
+            size: stored.size,
+            style: stored.style,
+            stacking: stored.stacking,
+            edges: stored.edges,
+        }
+    }
+}
 
-            grouped_letters.push(entry);
+#[cfg(feature = "serde")]
+impl From<GallifreyanWord> for WordLayout {
+    fn from(word: GallifreyanWord) -> Self {
+        WordLayout {
+            characters: word.to_gallifreyan_characters(),
+            edges: word.edge_shapes(),
+            letters: word.letters,
+            offsets: word.offsets,
+            size: word.size,
+            style: word.style,
+            stacking: word.stacking,
         }
+    }
+}
 
-        grouped_letters.iter().for_each(|gl| println!("{}", gl));
-        let parsed_letters = grouped_letters
+#[cfg(feature = "serde")]
+impl From<WordLayout> for GallifreyanWord {
+    /// Lays the letters out again, so that the word is always drawn consistently with its style.
+    /// Offsets that do not match the letters are counted in the transliteration instead.
+    fn from(layout: WordLayout) -> Self {
+        let word = GallifreyanWord::new(layout.letters)
+            .with_style(layout.style)
+            .with_stacking(layout.stacking);
+
+        match layout.offsets.len() == word.letters.len() {
+            true => word.with_offsets(layout.offsets),
+            false => word,
+        }
+    }
+}
+
+impl FromStr for GallifreyanWord {
+    type Err = ParseGallifreyanLetterError;
+
+    fn from_str(word: &str) -> Result<Self, Self::Err> {
+        let grouped_letters = Self::group_letters(word);
+        let letters = grouped_letters
+            .iter()
+            .map(|(byte_offset, char_offset, letter)| {
+                letter
+                    .parse::<GallifreyanLetter>()
+                    .map_err(|_| ParseGallifreyanLetterError::new(word, *byte_offset, *char_offset))
+            })
+            .collect::<Result<Vec<GallifreyanLetter>, ParseGallifreyanLetterError>>()?;
+        let offsets = grouped_letters
             .iter()
-            .map(|letter| letter.parse::<GallifreyanLetter>())
-            .collect::<Result<Vec<GallifreyanLetter>, ParseGallifreyanLetterError>>();
-
-        match parsed_letters {
-            Ok(letters) => {
-                let mut consonant_vowel_groups = Vec::<Vec<&GallifreyanLetter>>::new();
-                let mut letter_iter = letters.iter().peekable();
-
-                while let Some(current_letter) = letter_iter.next() {
-                    let entry = match current_letter.is_vowel() {
-                        true => vec![current_letter],
-                        false => match letter_iter.next_if(|next| next.is_vowel()) {
-                            Some(next_letter) => vec![current_letter, next_letter],
-                            None => vec![current_letter],
-                        },
+            .map(|(byte_offset, char_offset, _)| (*byte_offset, *char_offset))
+            .collect();
+
+        Ok(GallifreyanWord::new(letters).with_offsets(offsets))
+    }
+}
+
+impl TryFrom<&str> for GallifreyanWord {
+    type Error = ParseGallifreyanLetterError;
+
+    fn try_from(word: &str) -> Result<Self, Self::Error> {
+        word.parse()
+    }
+}
+
+impl GallifreyanWord {
+    /// Creates the word spelled by `letters`, drawn with the default style. The offsets of the
+    /// letters are counted in their transliteration.
+    pub fn new(letters: Vec<GallifreyanLetter>) -> GallifreyanWord {
+        let offsets = letters
+            .iter()
+            .scan(0, |offset, letter| {
+                let letter_offset = *offset;
+                *offset += letter.as_str().len();
+                Some((letter_offset, letter_offset))
+            })
+            .collect();
+
+        Self::laid_out(letters, Style::default(), false).with_offsets(offsets)
+    }
+
+    /// Draws the word with the proportions of `style`, resizing the word circle to its letter size.
+    pub fn with_style(self, style: Style) -> GallifreyanWord {
+        Self::laid_out(self.letters, style, self.stacking).with_offsets(self.offsets)
+    }
+
+    /// Stacks consecutive consonants of the same base in one position when `stacking` is set, each
+    /// drawn inside the one before it around the same centre, so that long words need a smaller
+    /// word circle. Only the innermost consonant of a stack can carry a vowel, and doubled
+    /// consonants are never stacked.
+    ///
+    /// A consonant carrying a vowel therefore ends its stack, so only runs of consonants with
+    /// nothing between them shrink the word. "Bartholomew" has no such run: its only consonants of
+    /// the same base next to each other, L and M, are split by the O carried by L, and it is laid
+    /// out as without stacking.
+    ///
+    /// Stacked words are not read back by [`recognise_word`](crate::recognition::recognise_word).
+    pub fn with_stacking(self, stacking: bool) -> GallifreyanWord {
+        Self::laid_out(self.letters, self.style, stacking).with_offsets(self.offsets)
+    }
+
+    /// Gives the letters the byte and `char` offsets `offsets`, one for each letter.
+    fn with_offsets(self, offsets: Vec<(usize, usize)>) -> GallifreyanWord {
+        GallifreyanWord { offsets, ..self }
+    }
+
+    /// Moves the offsets of the letters by those of the start of the word in a longer text.
+    pub(crate) fn shifted(self, byte_offset: usize, char_offset: usize) -> GallifreyanWord {
+        let offsets = self
+            .offsets
+            .iter()
+            .map(|(byte, char)| (byte + byte_offset, char + char_offset))
+            .collect();
+
+        self.with_offsets(offsets)
+    }
+
+    fn laid_out(letters: Vec<GallifreyanLetter>, style: Style, stacking: bool) -> GallifreyanWord {
+        let groups = Self::group_consonants_and_vowels(&letters, stacking);
+        let size = Self::size_ratio(groups.len()) * style.letter_size;
+        let (letter_size, positions) = Self::fit_groups(&groups, size, &style);
+
+        GallifreyanWord {
+            letters,
+            offsets: Vec::new(),
+            size,
+            style,
+            stacking,
+            letter_size,
+            positions,
+        }
+    }
+
+    /// The letter size and the angles of the groups of letters around a word circle of `size`,
+    /// keeping the strokes of different groups at least `MIN_CLEARANCE` letter sizes apart.
+    ///
+    /// The groups are spread evenly when that keeps them apart. Otherwise each group is given the
+    /// angle it needs from the one before it, and the angle left over is shared out evenly. When
+    /// the groups still do not fit, the letters are made smaller until they do, down to
+    /// `MIN_LETTER_SCALE` of the letter size of `style`.
+    fn fit_groups(
+        groups: &[Vec<(&GallifreyanLetter, bool)>],
+        size: f64,
+        style: &Style,
+    ) -> (f64, Vec<f64>) {
+        let count = groups.len();
+        let even = (0..count)
+            .map(|index| index as f64 * 2.0 * PI / count as f64 - FRAC_PI_2)
+            .collect::<Vec<f64>>();
+        let mut letter_size = style.letter_size;
+
+        loop {
+            let clearance = MIN_CLEARANCE * letter_size;
+            let strokes = |index: usize, position: f64| {
+                Self::group_strokes(&Self::group_characters(
+                    &groups[index],
+                    position,
+                    size,
+                    letter_size,
+                    style,
+                ))
+            };
+            let layout_clearance = |positions: &[f64]| {
+                Self::layout_clearance(
+                    &positions
+                        .iter()
+                        .enumerate()
+                        .map(|(index, position)| strokes(index, *position))
+                        .collect::<Vec<Vec<Shape>>>(),
+                    letter_size,
+                )
+            };
+
+            if count < 2 || layout_clearance(&even) >= clearance {
+                return (letter_size, even);
+            }
+
+            // The smallest angle from each group to the next keeping them apart.
+            let angles = (0..count)
+                .map(|index| {
+                    let first = strokes(index, 0.0);
+                    let apart = |angle: f64| {
+                        shapes::clearance(
+                            &first,
+                            &strokes((index + 1) % count, angle),
+                            Tessellation::MaxSegmentLength(CLEARANCE_STEP * letter_size),
+                        ) >= clearance
                     };
 
-                    consonant_vowel_groups.push(entry);
+                    (0..SPACING_STEPS)
+                        .fold((0.0, PI), |(low, high), _| {
+                            match apart((low + high) / 2.0) {
+                                true => (low, (low + high) / 2.0),
+                                false => ((low + high) / 2.0, high),
+                            }
+                        })
+                        .1
+                })
+                .collect::<Vec<f64>>();
+            let spare = (2.0 * PI - angles.iter().sum::<f64>()) / count as f64;
+            let positions = iter::once(-FRAC_PI_2)
+                .chain(angles.iter().scan(-FRAC_PI_2, |position, angle| {
+                    *position += angle + spare;
+                    Some(*position)
+                }))
+                .take(count)
+                .collect::<Vec<f64>>();
+
+            if spare >= 0.0 && layout_clearance(&positions) >= clearance
+                || letter_size * SHRINK_FACTOR < MIN_LETTER_SCALE * style.letter_size
+            {
+                return (letter_size, positions);
+            }
+            letter_size *= SHRINK_FACTOR;
+        }
+    }
+
+    /// The strokes of the characters that other groups of letters keep clear of. The lines of
+    /// line modifiers are left out, as they are connected around the other letters.
+    fn group_strokes(characters: &[GallifreyanCharacter]) -> Vec<Shape> {
+        characters
+            .iter()
+            .flat_map(|character| match character.modifier {
+                Some(Modifier::Line1 | Modifier::Line2 | Modifier::Line3) => {
+                    character.base_shapes()
                 }
+                _ => character.to_shapes(),
+            })
+            .collect()
+    }
 
-                let size = match consonant_vowel_groups.len() {
-                    0..=1 => 1.5 * Self::LETTER_SIZE,
-                    2 => 2.8 * Self::LETTER_SIZE,
-                    3..=4 => 3.0 * Self::LETTER_SIZE,
-                    _ => {
-                        (2.0 * (1.9 * Self::LETTER_SIZE))
-                            / (2.0 * (PI / consonant_vowel_groups.len() as f64).sin())
-                    }
-                };
+    /// The smallest distance between the strokes of two different groups of letters.
+    fn layout_clearance(groups: &[Vec<Shape>], letter_size: f64) -> f64 {
+        let tessellation = Tessellation::MaxSegmentLength(CLEARANCE_STEP * letter_size);
 
-                GallifreyanWord { letters, size }
-            }
-            Err(_) => panic!("The word could not be parsed to Gallifreyan!"),
+        (0..groups.len())
+            .flat_map(|first| (first + 1..groups.len()).map(move |second| (first, second)))
+            .map(|(first, second)| shapes::clearance(&groups[first], &groups[second], tessellation))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// The radius of a word circle holding `group_count` consonant and vowel groups, relative to
+    /// the letter size.
+    pub(crate) fn size_ratio(group_count: usize) -> f64 {
+        match group_count {
+            0..=1 => 1.5,
+            2 => 2.8,
+            3..=4 => 3.0,
+            _ => (2.0 * 1.9) / (2.0 * (PI / group_count as f64).sin()),
         }
     }
 
-    pub fn to_gallifreyan_characters(&self) -> Vec<GallifreyanCharacter> {
+    /// The radius of the word circle.
+    pub fn size(&self) -> f64 {
+        self.size
+    }
+
+    /// The size the letters are drawn at. It is the letter size of the style unless the letters
+    /// had to be made smaller to fit around the word circle.
+    pub fn letter_size(&self) -> f64 {
+        self.letter_size
+    }
+
+    /// The smallest distance between the strokes of two different groups of letters, leaving out
+    /// the lines of line modifiers. Returns `None` when there are fewer than two groups.
+    ///
+    /// Groups are kept at least a quarter of the letter size apart, unless even the smallest
+    /// letters do not fit around the word circle.
+    pub fn clearance(&self) -> Option<f64> {
+        let groups = Self::group_consonants_and_vowels(&self.letters, self.stacking);
+        let strokes = groups
+            .iter()
+            .zip(&self.positions)
+            .map(|(group, position)| {
+                Self::group_strokes(&Self::group_characters(
+                    group,
+                    *position,
+                    self.size,
+                    self.letter_size,
+                    &self.style,
+                ))
+            })
+            .collect::<Vec<Vec<Shape>>>();
+
+        (groups.len() > 1).then(|| Self::layout_clearance(&strokes, self.letter_size))
+    }
+
+    /// The radius of the smallest circle around the word's centre that encloses every stroke of
+    /// the word, including vowels drawn outside of the word circle.
+    pub fn outer_size(&self) -> f64 {
+        self.size + 1.5 * self.style.letter_size
+    }
+
+    /// Groups the characters of `word` into the strings of its Gallifreyan letters, keeping the
+    /// byte and `char` offsets of each group for error reporting.
+    fn group_letters(word: &str) -> Vec<(usize, usize, String)> {
         let mut grouped_letters = Vec::new();
-        let mut letter_iter = self.letters.iter().peekable();
+        let mut char_iter = word.char_indices().enumerate().peekable();
+
+        while let Some((char_offset, (byte_offset, current_letter))) = char_iter.next() {
+            let second_letter = match current_letter.to_ascii_uppercase() {
+                'C' | 'P' | 'W' | 'S' | 'T' | 'G' => Some('H'),
+                'Q' => Some('U'),
+                'N' => Some('G'),
+                _ => None,
+            };
+            let entry = match second_letter.and_then(|second_letter| {
+                char_iter.next_if(|(_, (_, next_letter))| {
+                    next_letter.eq_ignore_ascii_case(&second_letter)
+                })
+            }) {
+                Some((_, (_, next_letter))) => {
+                    current_letter.to_string() + &next_letter.to_string()
+                }
+                None => current_letter.to_string(),
+            };
+
+            grouped_letters.push((byte_offset, char_offset, entry));
+        }
+
+        grouped_letters
+    }
+
+    /// Groups each consonant with the vowel following it, collapsing a repeated letter into one
+    /// doubled letter. A vowel starting the word or following another vowel has no consonant to
+    /// attach to and makes a group of its own. With `stacking`, the consonants of a stack are
+    /// grouped together with the vowel of the innermost one.
+    fn group_consonants_and_vowels(
+        letters: &[GallifreyanLetter],
+        stacking: bool,
+    ) -> Vec<Vec<(&GallifreyanLetter, bool)>> {
+        let mut consonant_vowel_groups: Vec<Vec<(&GallifreyanLetter, bool)>> = Vec::new();
+        let mut letter_iter = letters.iter().peekable();
 
         while let Some(current_letter) = letter_iter.next() {
+            let current_doubled = letter_iter.next_if_eq(&current_letter).is_some();
             let entry = match current_letter.is_vowel() {
-                true => vec![current_letter],
+                true => vec![(current_letter, current_doubled)],
                 false => match letter_iter.next_if(|next| next.is_vowel()) {
-                    Some(next_letter) => vec![current_letter, next_letter],
-                    None => vec![current_letter],
+                    Some(next_letter) => {
+                        let next_doubled = letter_iter.next_if_eq(&next_letter).is_some();
+                        vec![
+                            (current_letter, current_doubled),
+                            (next_letter, next_doubled),
+                        ]
+                    }
+                    None => vec![(current_letter, current_doubled)],
                 },
             };
 
-            grouped_letters.push(entry);
+            match consonant_vowel_groups.last_mut() {
+                Some(stack) if stacking && Self::stacks_onto(stack, &entry) => stack.extend(entry),
+                _ => consonant_vowel_groups.push(entry),
+            }
         }
 
-        let step_size: f64 = 2.0 * PI / grouped_letters.iter().map(|_| 1.0).sum::<f64>();
+        consonant_vowel_groups
+    }
 
-        grouped_letters
+    /// Whether the consonant starting `group` can be stacked inside the consonants of `stack`.
+    fn stacks_onto(
+        stack: &[(&GallifreyanLetter, bool)],
+        group: &[(&GallifreyanLetter, bool)],
+    ) -> bool {
+        let base = |letter: &GallifreyanLetter| {
+            letter
+                .to_gallifreyan_character(Vector2::new(0.0, 0.0), 1.0, &Style::default())
+                .base
+        };
+        let stackable = |(letter, doubled): &(&GallifreyanLetter, bool)| {
+            !letter.is_vowel() && !doubled && base(letter) == base(stack[0].0)
+        };
+
+        stack.len() < MAX_STACK && stack.iter().all(stackable) && stackable(&group[0])
+    }
+
+    /// The characters of the word, placed counterclockwise from the bottom of the word circle with
+    /// one position per group of letters.
+    ///
+    /// A vowel in a group of its own stands on the word line like in the vowel chart: A outside the
+    /// word circle, O inside it, and E, I and U centred on the line, with the line of I pointing in
+    /// and the line of U pointing out. A vowel attached to a consonant is centred on the consonant
+    /// instead, except for A which keeps to the word line so that it is drawn outside the word
+    /// circle whatever the base of its consonant. The consonants of a stack are drawn from the
+    /// outermost in, the vowel coming just before the innermost one.
+    pub fn to_gallifreyan_characters(&self) -> Vec<GallifreyanCharacter> {
+        Self::group_consonants_and_vowels(&self.letters, self.stacking)
+            .iter()
+            .zip(&self.positions)
+            .flat_map(|(group, position)| {
+                Self::group_characters(group, *position, self.size, self.letter_size, &self.style)
+            })
+            .collect()
+    }
+
+    /// The characters of a group of letters drawn at `position` on a word circle of `size`, in
+    /// the order of [`GallifreyanWord::to_gallifreyan_characters`].
+    fn group_characters(
+        group: &[(&GallifreyanLetter, bool)],
+        position: f64,
+        size: f64,
+        letter_size: f64,
+        style: &Style,
+    ) -> Vec<GallifreyanCharacter> {
+        let line_point = Vector2::from_polar(size, position);
+        let (consonants, vowel) = match group.split_last() {
+            Some((last, rest)) if last.0.is_vowel() && !rest.is_empty() => (rest, Some(last)),
+            _ => (group, None),
+        };
+        let mut characters = consonants
             .iter()
             .enumerate()
-            .flat_map(|(index, group)| {
-                let position = (index as f64 * step_size) - FRAC_PI_2;
-                let mut characters = Vec::new();
-                let first_character = group
-                    .get(0)
-                    .expect("There should be at least one letter in each group.")
-                    .to_gallifreyan_character(
-                        Vector2::from_polar(self.size, position),
-                        Self::LETTER_SIZE,
-                    );
+            .map(|(depth, (letter, doubled))| {
+                let scale = STACK_RATIO.powi(depth as i32);
+                let style = match depth {
+                    0 => *style,
+                    _ => Self::stacked_style(size, letter_size, scale, style),
+                };
+
+                GallifreyanCharacter {
+                    doubled: *doubled,
+                    ..letter.to_gallifreyan_character(line_point, scale * letter_size, &style)
+                }
+            })
+            .collect::<Vec<GallifreyanCharacter>>();
+        let host = characters
+            .pop()
+            .expect("There should be at least one letter in each group.");
+
+        if let Some((letter, doubled)) = vowel {
+            let vowel = letter.to_gallifreyan_character(line_point, host.size, style);
+            let origin = match vowel.base.attaches_to_word_line() {
+                true => line_point,
+                false => line_point - host.base_vector(),
+            };
+            characters.push(GallifreyanCharacter {
+                doubled: *doubled,
+                origin,
+                ..vowel
+            });
+        }
+        characters.push(host);
+
+        characters
+    }
+
+    /// The style of a consonant stacked `scale` times the size of the outermost one, keeping the
+    /// centre of the outermost and opening it only where it reaches the word circle of `size`.
+    fn stacked_style(size: f64, letter_size: f64, scale: f64, style: &Style) -> Style {
+        let radius = scale * letter_size;
+        let opening = |depth: f64| {
+            let centre = size - depth * letter_size;
+            let cut = (size.powi(2) - centre.powi(2) - radius.powi(2)) / (2.0 * centre * radius);
+
+            cut.clamp(-1.0, 1.0).acos()
+        };
+
+        Style {
+            crescent_base_ratio: style.crescent_base_ratio / scale,
+            full_base_ratio: style.full_base_ratio / scale,
+            crescent_base_offset: opening(style.crescent_base_ratio),
+            quarter_base_offset: opening(0.0),
+            ..*style
+        }
+    }
+
+    /// The index in `letters` of the letter drawn by each character, in the order of
+    /// [`GallifreyanWord::to_gallifreyan_characters`]. A doubled letter has the index of its first
+    /// letter.
+    fn character_letter_indices(&self) -> Vec<usize> {
+        let mut index = 0;
+
+        Self::group_consonants_and_vowels(&self.letters, self.stacking)
+            .iter()
+            .flat_map(|group| {
+                let mut indices = group
+                    .iter()
+                    .map(|(_, doubled)| {
+                        let letter_index = index;
+                        index += if *doubled { 2 } else { 1 };
+                        letter_index
+                    })
+                    .collect::<Vec<usize>>();
 
-                if let Some(letter) = group.get(1) {
-                    characters.push(letter.to_gallifreyan_character(
-                        Vector2::from_polar(self.size, position) - first_character.base_vector(),
-                        Self::LETTER_SIZE,
-                    ));
+                // The vowel of a group is drawn before its consonant, the innermost of a stack.
+                if group.len() > 1 && group[group.len() - 1].0.is_vowel() {
+                    indices.swap(group.len() - 2, group.len() - 1);
                 }
-                characters.push(first_character);
+                indices
+            })
+            .collect()
+    }
 
-                characters
+    /// Lays the word out around its centre, keeping the letter and part drawn by every shape.
+    /// The lines of line modifiers are connected to each other or to the word circle.
+    pub fn to_layout(&self) -> Layout {
+        let modifier_shapes = connected_modifier_shapes(&[(Vector2::new(0.0, 0.0), self)])
+            .pop()
+            .expect("There should be the modifier shapes of the word.");
+
+        self.layout_with_modifiers(modifier_shapes)
+    }
+
+    /// Lays the word out around its centre, drawing the modifier of each character, in the order
+    /// of [`GallifreyanWord::to_gallifreyan_characters`], with `modifier_shapes`.
+    pub(crate) fn layout_with_modifiers(&self, modifier_shapes: Vec<Vec<Shape>>) -> Layout {
+        let characters = self.to_gallifreyan_characters();
+        let letter_elements = characters
+            .iter()
+            .zip(self.character_letter_indices())
+            .zip(modifier_shapes)
+            .flat_map(|((character, index), modifier_shapes)| {
+                let letter = self.letters[index];
+                let (base_role, modifier_role) = match letter.is_vowel() {
+                    true => (Role::Vowel, Role::Vowel),
+                    false => (Role::Base, Role::Modifier),
+                };
+
+                character
+                    .base_shapes()
+                    .into_iter()
+                    .map(move |shape| Element::new(shape, base_role))
+                    .chain(
+                        modifier_shapes
+                            .into_iter()
+                            .map(move |shape| Element::new(shape, modifier_role)),
+                    )
+                    .map(move |element| {
+                        element.with_letter(index, self.offsets[index], letter, character)
+                    })
+            });
+        let edge_elements = self
+            .edge_shapes()
+            .into_iter()
+            .map(|shape| Element::new(shape, Role::Edge));
+
+        Layout::new(
+            letter_elements
+                .chain(edge_elements)
+                .map(|element| element.with_word(0))
+                .collect(),
+        )
+    }
+
+    /// The letters of the word, in reading order.
+    pub fn letters(&self) -> &[GallifreyanLetter] {
+        &self.letters
+    }
+
+    /// The byte and `char` offsets of each letter in the text the word was parsed from, in the
+    /// order of [`GallifreyanWord::letters`]. The letters of a word in a sentence are counted from
+    /// the start of the sentence.
+    pub fn offsets(&self) -> &[(usize, usize)] {
+        &self.offsets
+    }
+
+    /// Reads the Latin transliteration back from the characters of the word.
+    pub fn to_text(&self) -> String {
+        Self::characters_to_text(&self.to_gallifreyan_characters())
+            .expect("Every character of a word should decode to a letter.")
+    }
+
+    /// Reads the Latin transliteration of `characters`, ordered as returned by
+    /// [`GallifreyanWord::to_gallifreyan_characters`]. Returns `None` when a character does not
+    /// decode to a letter.
+    pub fn characters_to_text(characters: &[GallifreyanCharacter]) -> Option<String> {
+        let letters = characters
+            .iter()
+            .map(|character| character.to_letter().map(|letter| (character, letter)))
+            .collect::<Option<Vec<(&GallifreyanCharacter, GallifreyanLetter)>>>()?;
+        let mut ordered_letters = Vec::new();
+        let mut letter_iter = letters.into_iter().peekable();
+
+        while let Some((character, letter)) = letter_iter.next() {
+            // A vowel attached to a consonant is drawn before it, along the same angle.
+            let consonant = letter_iter.next_if(|(next_character, next_letter)| {
+                letter.is_vowel()
+                    && !next_letter.is_vowel()
+                    && (next_character.origin.phi() - character.origin.phi()).abs() < 1e-9
+            });
+
+            ordered_letters.extend(consonant);
+            ordered_letters.push((character, letter));
+        }
+
+        let text = ordered_letters
+            .iter()
+            .flat_map(|(character, letter)| match character.doubled {
+                true => vec![letter.as_str(); 2],
+                false => vec![letter.as_str()],
             })
-            .collect::<Vec<GallifreyanCharacter>>()
+            .collect::<String>();
+
+        Some(text)
+    }
+
+    /// The shapes of every character followed by the edges of the word circle, with the lines of
+    /// line modifiers connected like in [`GallifreyanWord::to_layout`].
+    pub fn to_shapes(&self) -> Vec<Shape> {
+        self.to_layout().to_shapes()
     }
 
     pub fn draw_edges(&self) -> Vec<Vec<(f32, f32)>> {
-        let characters_with_edges = self
+        self.draw_edges_with(Tessellation::default())
+    }
+
+    /// Draws the edges like [`draw_edges`](Self::draw_edges), tessellated with `tessellation`.
+    pub fn draw_edges_with(&self, tessellation: Tessellation) -> Vec<Vec<(f32, f32)>> {
+        self.edge_shapes()
+            .iter()
+            .map(|shape| shape.tessellate(tessellation))
+            .collect()
+    }
+
+    /// The arcs of the word circle between the characters cutting into it.
+    pub fn edge_shapes(&self) -> Vec<Shape> {
+        let centre = Vector2::from_polar(0.0, 0.0);
+        let mut characters_with_edges = self
             .to_gallifreyan_characters()
             .into_iter()
             .filter(|gallifreyan_character| gallifreyan_character.has_edge())
             .collect::<Vec<GallifreyanCharacter>>();
+        // Only the outermost consonant of a stack cuts into the word circle.
+        characters_with_edges
+            .dedup_by(|inner, outer| (inner.origin.phi() - outer.origin.phi()).abs() < 1e-9);
 
         if characters_with_edges.is_empty() {
-            return vec![draw_base(
-                Vector2::from_polar(0.0, 0.0),
-                self.size,
-                (0.0, 2.0 * PI),
-                0.0,
-            )];
+            return vec![Shape::Circle {
+                centre,
+                radius: self.size,
+            }];
         }
 
-        let mut edges: Vec<Vec<(f32, f32)>> = characters_with_edges
+        let mut edges: Vec<Shape> = characters_with_edges
             .as_slice()
             .windows(2)
             .map(|letters| {
@@ -498,12 +1340,7 @@ impl GallifreyanWord {
                     .starting_angle()
                     .expect("The Gallifreyan character should have an edge.");
 
-                draw_base(
-                    Vector2::from_polar(0.0, 0.0),
-                    letters[0].origin.rho(),
-                    (edge1, edge2),
-                    0.0,
-                )
+                Shape::arc(centre, self.size, edge1, edge2)
             })
             .collect();
 
@@ -523,13 +1360,311 @@ impl GallifreyanWord {
                 .expect("The Gallifreyan character should have an edge."),
         };
 
-        edges.push(draw_base(
-            Vector2::from_polar(0.0, 0.0),
-            self.size,
-            (edge1, edge2),
-            0.0,
-        ));
+        edges.push(Shape::arc(centre, self.size, edge1, edge2));
 
         edges
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sentences::GallifreyanSentence;
+
+    #[test]
+    fn parse_errors_report_the_offsets_of_the_character() {
+        let error = "cafe\u{301}".parse::<GallifreyanWord>().unwrap_err();
+
+        assert_eq!(error.character(), '\u{301}');
+        assert_eq!((error.byte_offset(), error.char_offset()), (4, 4));
+        assert_eq!(error.grapheme(), "e\u{301}");
+
+        // The curly quote is three bytes long, so the offsets of the digit differ.
+        let error = "\u{201C}hello\u{201D} wor1d"
+            .parse::<GallifreyanSentence>()
+            .err()
+            .unwrap();
+
+        assert_eq!(error.character(), '1');
+        assert_eq!((error.byte_offset(), error.char_offset()), (15, 11));
+        assert_eq!(error.grapheme(), "1");
+    }
+
+    #[test]
+    fn repeated_letters_are_drawn_as_one_doubled_character() {
+        let word = "tennant".parse::<GallifreyanWord>().unwrap();
+        let characters = word.to_gallifreyan_characters();
+        let doubled = characters
+            .iter()
+            .filter(|character| character.doubled)
+            .collect::<Vec<&GallifreyanCharacter>>();
+
+        assert_eq!(characters.len(), 6);
+        assert_eq!(doubled.len(), 1);
+        assert_eq!(doubled[0].to_letter(), Some(GallifreyanLetter::N));
+        assert_eq!(word.to_text(), "TENNANT");
+
+        match doubled[0].base_shapes()[..] {
+            [Shape::Circle {
+                centre: outer_centre,
+                radius: outer_radius,
+            }, Shape::Circle {
+                centre: inner_centre,
+                radius: inner_radius,
+            }] => {
+                assert!((outer_centre - inner_centre).rho() < 1e-9);
+                assert!(
+                    (inner_radius - word.style().double_base_ratio * outer_radius).abs() < 1e-9
+                );
+            }
+            ref shapes => panic!("Expected two concentric circles, got {shapes:?}."),
+        }
+    }
+
+    #[test]
+    fn every_letter_decodes_from_its_glyph() {
+        "A E I O U B CH D G H F J PH K L C N P M T WH SH R V W S TH GH Y Z Q QU X NG"
+            .split(' ')
+            .for_each(|text| {
+                let letter = text.parse::<GallifreyanLetter>().unwrap();
+                let character =
+                    letter.to_gallifreyan_character(Vector2::new(4.0, 0.0), 2.0, &Style::default());
+
+                assert_eq!(letter.as_str(), text);
+                assert_eq!(character.to_letter(), Some(letter), "{text}");
+            });
+    }
+
+    #[test]
+    fn glyphs_without_a_letter_do_not_decode() {
+        assert_eq!(
+            GallifreyanLetter::from_glyph(&Base::Crescent, Some(&Modifier::Dot1)),
+            None
+        );
+        assert_eq!(
+            GallifreyanLetter::from_glyph(&Base::Moon(0.0), Some(&Modifier::Line1)),
+            None
+        );
+        assert_eq!(
+            GallifreyanLetter::from_glyph(&Base::Full, Some(&Modifier::VowelLine1(0.0))),
+            None
+        );
+    }
+
+    #[test]
+    fn words_read_back_as_their_letters() {
+        [
+            "chthonic",
+            "gallifrey",
+            "aeon",
+            "idea",
+            "quartz",
+            "whinging",
+        ]
+        .iter()
+        .for_each(|text| {
+            let word = text.parse::<GallifreyanWord>().unwrap();
+
+            assert_eq!(word.to_text(), text.to_uppercase());
+            assert_eq!(
+                word.clone().with_stacking(true).to_text(),
+                text.to_uppercase()
+            );
+        });
+    }
+
+    /// The centre of the outer stroke of the base of `character`.
+    fn stroke_centre(character: &GallifreyanCharacter) -> Vector2 {
+        match character.base_shapes()[0] {
+            Shape::Arc { centre, .. } | Shape::Circle { centre, .. } => centre,
+            shape => panic!("Expected an arc or a circle, got {shape:?}."),
+        }
+    }
+
+    #[test]
+    fn attached_vowels_are_placed_by_their_consonant() {
+        ["b", "j", "t", "th"].iter().for_each(|consonant| {
+            ["a", "e", "i", "o", "u"].iter().for_each(|vowel| {
+                let word = format!("{consonant}{vowel}")
+                    .parse::<GallifreyanWord>()
+                    .unwrap();
+                let characters = word.to_gallifreyan_characters();
+                let (vowel_centre, host) = (stroke_centre(&characters[0]), &characters[1]);
+                let moon = Vector2::from_polar(
+                    word.style().moon_base_ratio * word.letter_size(),
+                    host.origin.phi(),
+                );
+                let expected = match *vowel {
+                    // A keeps to the word line, outside the word circle.
+                    "a" => host.origin + moon,
+                    "o" => stroke_centre(host) - moon,
+                    _ => stroke_centre(host),
+                };
+
+                assert!(
+                    (vowel_centre - expected).rho() < 1e-9,
+                    "{consonant}{vowel}: {vowel_centre:?} is not at {expected:?}"
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn groups_keep_their_clearance() {
+        let style = Style::default();
+
+        ["doctor", "jnjnjn", "thynthyn", "mississippi"]
+            .iter()
+            .for_each(|text| {
+                let word = text.parse::<GallifreyanWord>().unwrap();
+                let groups = GallifreyanWord::group_consonants_and_vowels(&word.letters, false);
+
+                assert!(word.clearance().unwrap() >= MIN_CLEARANCE * word.letter_size());
+
+                // Word circles too small for the letters make them shrink to keep their clearance.
+                [1.5, 2.0].iter().for_each(|ratio| {
+                    let size = ratio * style.letter_size;
+                    let (letter_size, positions) =
+                        GallifreyanWord::fit_groups(&groups, size, &style);
+                    let strokes = groups
+                        .iter()
+                        .zip(&positions)
+                        .map(|(group, position)| {
+                            GallifreyanWord::group_strokes(&GallifreyanWord::group_characters(
+                                group,
+                                *position,
+                                size,
+                                letter_size,
+                                &style,
+                            ))
+                        })
+                        .collect::<Vec<Vec<Shape>>>();
+
+                    assert!(letter_size < style.letter_size, "{text} at {ratio}");
+                    assert!(letter_size >= MIN_LETTER_SCALE * style.letter_size);
+                    assert!(
+                        GallifreyanWord::layout_clearance(&strokes, letter_size)
+                            >= MIN_CLEARANCE * letter_size,
+                        "{text} at {ratio}"
+                    );
+                });
+            });
+    }
+
+    #[test]
+    fn stacking_shrinks_runs_of_consonants_with_the_same_base() {
+        let word = "lmnpkt".parse::<GallifreyanWord>().unwrap();
+        let stacked = word.clone().with_stacking(true);
+        let sizes = stacked
+            .to_gallifreyan_characters()
+            .iter()
+            .map(|character| character.size / stacked.letter_size())
+            .collect::<Vec<f64>>();
+        let expected = [1.0, STACK_RATIO, STACK_RATIO.powi(2), 1.0, STACK_RATIO, 1.0];
+
+        assert!(stacked.size() < word.size());
+        assert_eq!(sizes.len(), expected.len());
+        sizes
+            .iter()
+            .zip(expected)
+            .for_each(|(size, expected)| assert!((size - expected).abs() < 1e-9));
+    }
+
+    #[test]
+    fn stacks_are_limited() {
+        let group_lengths = |text: &str| {
+            let word = text.parse::<GallifreyanWord>().unwrap();
+
+            GallifreyanWord::group_consonants_and_vowels(&word.letters, true)
+                .iter()
+                .map(Vec::len)
+                .collect::<Vec<usize>>()
+        };
+
+        // No more than MAX_STACK consonants share a position.
+        assert_eq!(group_lengths("lmnpk"), [MAX_STACK, 2]);
+        // Only the innermost consonant carries a vowel, and doubled consonants are not stacked.
+        assert_eq!(group_lengths("lmna"), [4]);
+        assert_eq!(group_lengths("lomn"), [2, 2]);
+        assert_eq!(group_lengths("llmn"), [1, 2]);
+        assert_eq!(group_lengths("bartholomew"), [2, 1, 2, 2, 2, 1]);
+    }
+
+    #[test]
+    fn vowels_without_a_consonant_stand_on_the_word_line() {
+        ["aeon", "idea", "ouija"].iter().for_each(|text| {
+            let word = text.parse::<GallifreyanWord>().unwrap();
+            let moon = word.style().moon_base_ratio * word.letter_size();
+            let groups = GallifreyanWord::group_consonants_and_vowels(&word.letters, false);
+
+            groups
+                .iter()
+                .zip(&word.positions)
+                .filter(|(group, _)| group.len() == 1 && group[0].0.is_vowel())
+                .for_each(|(group, position)| {
+                    let letter = group[0].0;
+                    let character = GallifreyanWord::group_characters(
+                        group,
+                        *position,
+                        word.size(),
+                        word.letter_size(),
+                        word.style(),
+                    )
+                    .remove(0);
+                    let rho = stroke_centre(&character).rho();
+                    let expected = match letter {
+                        GallifreyanLetter::A => word.size() + moon,
+                        GallifreyanLetter::O => word.size() - moon,
+                        _ => word.size(),
+                    };
+
+                    assert!((character.origin.rho() - word.size()).abs() < 1e-9);
+                    assert!((rho - expected).abs() < 1e-9, "{letter} in {text}");
+
+                    // The line of I points into the word circle and that of U out of it.
+                    let tip = character
+                        .modifier_shapes()
+                        .iter()
+                        .flat_map(|shape| match *shape {
+                            Shape::Segment { start, end } => vec![start, end],
+                            _ => Vec::new(),
+                        })
+                        .max_by(|first, second| {
+                            (*first - character.origin)
+                                .rho()
+                                .total_cmp(&(*second - character.origin).rho())
+                        });
+                    match letter {
+                        GallifreyanLetter::I => assert!(tip.unwrap().rho() < word.size()),
+                        GallifreyanLetter::U => assert!(tip.unwrap().rho() > word.size()),
+                        _ => assert_eq!(tip, None),
+                    }
+                });
+        });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialized_words_store_their_style_once() {
+        let style = Style {
+            letter_size: 3.0,
+            ..Style::default()
+        };
+        let word = "doctor"
+            .parse::<GallifreyanWord>()
+            .unwrap()
+            .with_style(style);
+        let json = serde_json::to_string(&word).unwrap();
+
+        assert_eq!(json.matches("letter_size").count(), 1);
+        assert_eq!(
+            serde_json::from_str::<GallifreyanWord>(&json).unwrap(),
+            word
+        );
+        serde_json::from_str::<WordLayout>(&json)
+            .unwrap()
+            .characters
+            .iter()
+            .for_each(|character| assert_eq!(character.style, style));
+    }
+}
@@ -2,6 +2,37 @@
 //! alphabet](https://github.com/JosephGonzalez03/gallifreyan/blob/main/Gallifreyan.pdf). It
 //! provides the alphabet's letters as well as methods to decompose them into vectors of f32 cartesian points.  
 
+/// Colouring of the parts of a drawing.
+pub mod colours;
+mod connections;
+/// DXF export of words and sentences for CAD tools.
+#[cfg(feature = "dxf")]
+pub mod dxf;
 pub mod glyphs;
+/// The `Layout` implementation.
+pub mod layout;
 /// The `GallifreyanLetter` implementation.
 pub mod letters;
+/// The `GallifreyanNumber` implementation.
+pub mod numbers;
+/// PDF export of words and sentences for printing.
+#[cfg(feature = "pdf")]
+pub mod pdf;
+/// G-code and HPGL export of words and sentences for pen plotters and engravers.
+#[cfg(feature = "plotter")]
+pub mod plotter;
+/// Recognition of words drawn in PNG images.
+#[cfg(feature = "raster")]
+pub mod raster;
+/// Reading words back from their shapes.
+pub mod recognition;
+/// The `GallifreyanSentence` implementation.
+pub mod sentences;
+/// The `Shape` implementation.
+pub mod shapes;
+/// SVG export of words and sentences, and recognition of words drawn in SVG.
+#[cfg(feature = "svg")]
+pub mod svg;
+/// TikZ export of words and sentences for LaTeX documents.
+#[cfg(feature = "tikz")]
+pub mod tikz;
@@ -0,0 +1,300 @@
+use crate::shapes::{Shape, Tessellation};
+use core::fmt;
+use geomath::prelude::coordinates::Polar;
+use geomath::vector::Vector2;
+use std::error::Error;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_8};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The error returned when text is not a number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseGallifreyanNumberError {
+    /// A character that is not a digit, or a sign or decimal point out of place, with its byte
+    /// and `char` offsets in the parsed text.
+    UnexpectedCharacter {
+        character: char,
+        byte_offset: usize,
+        char_offset: usize,
+    },
+    /// The text has no digits.
+    NoDigits,
+    /// The decimal point is not followed by any digit.
+    TrailingPoint,
+}
+
+impl Display for ParseGallifreyanNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGallifreyanNumberError::UnexpectedCharacter {
+                character,
+                char_offset,
+                ..
+            } => write!(f, "unexpected {:?} at character {}", character, char_offset),
+            ParseGallifreyanNumberError::NoDigits => write!(f, "the number has no digits"),
+            ParseGallifreyanNumberError::TrailingPoint => {
+                write!(f, "the decimal point has no digits after it")
+            }
+        }
+    }
+}
+
+impl Error for ParseGallifreyanNumberError {}
+
+/// A number written in Gallifreyan numeral notation.
+///
+/// The digits are drawn as concentric bands inside the number circle, the most significant digit
+/// innermost. Each band holds one radial line per unit of the digit and a small ring for a five.
+/// A dot on the ring between the integer and fractional digits marks the decimal point, and a line
+/// leaving the number circle marks a negative number.
+pub struct GallifreyanNumber {
+    negative: bool,
+    digits: Vec<u8>,
+    decimals: usize,
+    size: f64,
+}
+
+impl FromStr for GallifreyanNumber {
+    type Err = ParseGallifreyanNumberError;
+
+    fn from_str(number: &str) -> Result<Self, Self::Err> {
+        let mut negative = false;
+        let mut digits = Vec::new();
+        let mut decimal_point = None;
+
+        for (char_offset, (byte_offset, character)) in number.char_indices().enumerate() {
+            match character {
+                '-' if char_offset == 0 => negative = true,
+                '.' if decimal_point.is_none() => decimal_point = Some(digits.len()),
+                '0'..='9' => digits.push(character as u8 - b'0'),
+                _ => {
+                    return Err(ParseGallifreyanNumberError::UnexpectedCharacter {
+                        character,
+                        byte_offset,
+                        char_offset,
+                    })
+                }
+            }
+        }
+
+        if digits.is_empty() {
+            return Err(ParseGallifreyanNumberError::NoDigits);
+        }
+        if decimal_point == Some(digits.len()) {
+            return Err(ParseGallifreyanNumberError::TrailingPoint);
+        }
+
+        let decimals = decimal_point.map_or(0, |point| digits.len() - point);
+        let size = (digits.len() + 1) as f64 * Self::RING_SPACING;
+
+        Ok(GallifreyanNumber {
+            negative,
+            digits,
+            decimals,
+            size,
+        })
+    }
+}
+
+impl TryFrom<&str> for GallifreyanNumber {
+    type Error = ParseGallifreyanNumberError;
+
+    fn try_from(number: &str) -> Result<Self, Self::Error> {
+        number.parse()
+    }
+}
+
+impl GallifreyanNumber {
+    const RING_SPACING: f64 = 2.0;
+
+    /// The radius of the number circle.
+    pub fn size(&self) -> f64 {
+        self.size
+    }
+
+    /// The shapes of the number circle, the rings and the digits.
+    pub fn to_shapes(&self) -> Vec<Shape> {
+        let mut shapes = self.edge_shapes();
+        shapes.append(&mut self.digit_shapes());
+        shapes.append(&mut self.dot_shapes());
+        shapes
+    }
+
+    /// Draws the number circle, the rings between the digits and the line of a negative number.
+    pub fn draw_edges(&self) -> Vec<Vec<(f32, f32)>> {
+        self.draw_edges_with(Tessellation::default())
+    }
+
+    /// Draws the edges like [`draw_edges`](Self::draw_edges), tessellated with `tessellation`.
+    pub fn draw_edges_with(&self, tessellation: Tessellation) -> Vec<Vec<(f32, f32)>> {
+        tessellate(&self.edge_shapes(), tessellation)
+    }
+
+    /// Draws the lines and rings of every digit.
+    pub fn draw_digits(&self) -> Vec<Vec<(f32, f32)>> {
+        self.draw_digits_with(Tessellation::default())
+    }
+
+    /// Draws the digits like [`draw_digits`](Self::draw_digits), tessellated with `tessellation`.
+    pub fn draw_digits_with(&self, tessellation: Tessellation) -> Vec<Vec<(f32, f32)>> {
+        tessellate(&self.digit_shapes(), tessellation)
+    }
+
+    /// Draws the decimal point, if the number has one.
+    pub fn draw_dots(&self) -> Vec<Vec<(f32, f32)>> {
+        self.draw_dots_with(Tessellation::default())
+    }
+
+    /// Draws the decimal point like [`draw_dots`](Self::draw_dots), tessellated with
+    /// `tessellation`.
+    pub fn draw_dots_with(&self, tessellation: Tessellation) -> Vec<Vec<(f32, f32)>> {
+        tessellate(&self.dot_shapes(), tessellation)
+    }
+
+    fn edge_shapes(&self) -> Vec<Shape> {
+        let mut edges = (0..=self.digits.len())
+            .map(|ring| Shape::Circle {
+                centre: Vector2::from_polar(0.0, 0.0),
+                radius: self.ring_size(ring),
+            })
+            .collect::<Vec<Shape>>();
+
+        if self.negative {
+            edges.push(Shape::Segment {
+                start: Vector2::from_polar(self.size, -FRAC_PI_2),
+                end: Vector2::from_polar(self.size + Self::RING_SPACING, -FRAC_PI_2),
+            });
+        }
+
+        edges
+    }
+
+    fn digit_shapes(&self) -> Vec<Shape> {
+        self.digits
+            .iter()
+            .enumerate()
+            .flat_map(|(band, digit)| {
+                let inner_size = self.ring_size(band);
+                let outer_size = self.ring_size(band + 1);
+                let lines = digit % 5;
+
+                let mut shapes = (0..lines)
+                    .map(|line| {
+                        let angle =
+                            -FRAC_PI_2 + (line as f64 - (lines - 1) as f64 / 2.0) * FRAC_PI_8;
+                        Shape::Segment {
+                            start: Vector2::from_polar(inner_size, angle),
+                            end: Vector2::from_polar(outer_size, angle),
+                        }
+                    })
+                    .collect::<Vec<Shape>>();
+
+                if *digit >= 5 {
+                    shapes.push(Shape::Circle {
+                        centre: Vector2::from_polar((inner_size + outer_size) / 2.0, FRAC_PI_2),
+                        radius: Self::RING_SPACING / 4.0,
+                    });
+                }
+
+                shapes
+            })
+            .collect()
+    }
+
+    fn dot_shapes(&self) -> Vec<Shape> {
+        match self.decimals {
+            0 => Vec::new(),
+            decimals => vec![Shape::Dot {
+                centre: Vector2::from_polar(self.ring_size(self.digits.len() - decimals), 0.0),
+            }],
+        }
+    }
+
+    fn ring_size(&self, ring: usize) -> f64 {
+        (ring + 1) as f64 * Self::RING_SPACING
+    }
+}
+
+fn tessellate(shapes: &[Shape], tessellation: Tessellation) -> Vec<Vec<(f32, f32)>> {
+    shapes
+        .iter()
+        .map(|shape| shape.tessellate(tessellation))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_without_digits_are_errors() {
+        ["", "-", ".", "-."].iter().for_each(|number| {
+            assert_eq!(
+                number.parse::<GallifreyanNumber>().err(),
+                Some(ParseGallifreyanNumberError::NoDigits)
+            );
+        });
+    }
+
+    #[test]
+    fn trailing_decimal_point_is_an_error() {
+        assert_eq!(
+            "1.".parse::<GallifreyanNumber>().err(),
+            Some(ParseGallifreyanNumberError::TrailingPoint)
+        );
+        assert_eq!(".5".parse::<GallifreyanNumber>().unwrap().decimals, 1);
+    }
+
+    #[test]
+    fn drawings_use_the_given_tessellation() {
+        let number = "12".parse::<GallifreyanNumber>().unwrap();
+        let points = |tessellation| {
+            number
+                .draw_edges_with(tessellation)
+                .iter()
+                .map(Vec::len)
+                .sum::<usize>()
+        };
+
+        assert_eq!(
+            number.draw_edges(),
+            number.draw_edges_with(Tessellation::default())
+        );
+        assert!(points(Tessellation::MaxSegmentLength(1.0)) < points(Tessellation::default()));
+    }
+
+    #[test]
+    fn second_decimal_point_is_unexpected() {
+        let error = "1.2.3".parse::<GallifreyanNumber>().err().unwrap();
+
+        assert_eq!(
+            error,
+            ParseGallifreyanNumberError::UnexpectedCharacter {
+                character: '.',
+                byte_offset: 3,
+                char_offset: 3,
+            }
+        );
+        assert_eq!(error.to_string(), "unexpected '.' at character 3");
+    }
+
+    #[test]
+    fn minus_sign_after_the_start_is_unexpected() {
+        assert_eq!(
+            "12-3".parse::<GallifreyanNumber>().err(),
+            Some(ParseGallifreyanNumberError::UnexpectedCharacter {
+                character: '-',
+                byte_offset: 2,
+                char_offset: 2,
+            })
+        );
+        assert_eq!(
+            "--1".parse::<GallifreyanNumber>().err(),
+            Some(ParseGallifreyanNumberError::UnexpectedCharacter {
+                character: '-',
+                byte_offset: 1,
+                char_offset: 1,
+            })
+        );
+    }
+}
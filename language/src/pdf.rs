@@ -0,0 +1,384 @@
+use crate::colours::{Colour, Colouring};
+use crate::layout::Layout;
+use crate::letters::*;
+use crate::numbers::*;
+use crate::sentences::*;
+use crate::shapes::Shape;
+use geomath::vector::Vector2;
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::io::Write;
+
+const MARGIN: f64 = 1.0;
+/// The height of the band holding the caption, relative to its font size.
+const CAPTION_LEADING: f64 = 2.0;
+/// The widths of the printable ASCII characters in Helvetica, in thousandths of the font size.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+const DEFAULT_WIDTH: u16 = 556;
+/// The smallest side in points of the square the drawing is scaled to, kept when the margins and
+/// caption leave no room for it.
+const MIN_SIDE: f64 = 1.0;
+
+/// A paper size. Pages are portrait; use `Custom` for any other page.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Paper {
+    A3,
+    A4,
+    A5,
+    Letter,
+    Legal,
+    /// A page `width` wide and `height` high, in points.
+    Custom {
+        width: f64,
+        height: f64,
+    },
+}
+
+impl Paper {
+    /// The width and height of the page in points.
+    pub fn dimensions(&self) -> (f64, f64) {
+        match *self {
+            Paper::A3 => (841.89, 1190.55),
+            Paper::A4 => (595.28, 841.89),
+            Paper::A5 => (419.53, 595.28),
+            Paper::Letter => (612.0, 792.0),
+            Paper::Legal => (612.0, 1008.0),
+            Paper::Custom { width, height } => (width, height),
+        }
+    }
+}
+
+/// The page and presentation attributes of a PDF document. Lengths are in points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PdfOptions {
+    pub paper: Paper,
+    /// The space left blank on every side of the page.
+    pub margin: f64,
+    /// The colour of the strokes and dots.
+    pub colour: Colour,
+    /// The colour filling the whole page, if any.
+    pub background: Option<Colour>,
+    pub stroke_width: f64,
+    pub dot_radius: f64,
+    /// The Latin text written under the drawing, if any.
+    pub caption: Option<String>,
+    /// The font size of the caption.
+    pub caption_size: f64,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        PdfOptions {
+            paper: Paper::A4,
+            margin: 72.0,
+            colour: Colour::BLACK,
+            background: None,
+            stroke_width: 1.0,
+            dot_radius: 2.0,
+            caption: None,
+            caption_size: 24.0,
+        }
+    }
+}
+
+/// Writes a one page PDF document of the word, centred on its word circle.
+pub fn word_to_pdf(word: &GallifreyanWord, options: &PdfOptions) -> Vec<u8> {
+    shapes_to_pdf(&word.to_shapes(), word.outer_size() + MARGIN, options)
+}
+
+/// Writes a one page PDF document of the sentence, centred on its sentence circle.
+pub fn sentence_to_pdf(sentence: &GallifreyanSentence, options: &PdfOptions) -> Vec<u8> {
+    shapes_to_pdf(&sentence.to_shapes(), sentence.size() + MARGIN, options)
+}
+
+/// Writes a one page PDF document of the number, centred on its number circle.
+pub fn number_to_pdf(number: &GallifreyanNumber, options: &PdfOptions) -> Vec<u8> {
+    shapes_to_pdf(&number.to_shapes(), number.size() + 3.0 * MARGIN, options)
+}
+
+/// Writes a one page PDF document of the shapes, scaling the square spanning `size` around the
+/// origin to the largest square fitting between the margins and above the caption.
+pub fn shapes_to_pdf(shapes: &[Shape], size: f64, options: &PdfOptions) -> Vec<u8> {
+    let shapes = shapes
+        .iter()
+        .map(|shape| (*shape, options.colour))
+        .collect::<Vec<(Shape, Colour)>>();
+
+    coloured_shapes_to_pdf(&shapes, size, options)
+}
+
+/// Writes a one page PDF document of the layout like `shapes_to_pdf`, drawing every element in the
+/// colour `colouring` picks for it instead of `options.colour`.
+pub fn layout_to_pdf_with(
+    layout: &Layout,
+    size: f64,
+    options: &PdfOptions,
+    colouring: &impl Colouring,
+) -> Vec<u8> {
+    let shapes = layout
+        .elements()
+        .iter()
+        .map(|element| (element.shape, colouring.colour(element)))
+        .collect::<Vec<(Shape, Colour)>>();
+
+    coloured_shapes_to_pdf(&shapes, size, options)
+}
+
+/// Writes a one page PDF document of the shapes like `shapes_to_pdf`, drawing each shape in its own
+/// colour instead of `options.colour`. Arcs and circles are drawn as Bézier curves and the caption
+/// in Helvetica, so the document needs no embedded font or image.
+pub fn coloured_shapes_to_pdf(
+    shapes: &[(Shape, Colour)],
+    size: f64,
+    options: &PdfOptions,
+) -> Vec<u8> {
+    let (width, height) = options.paper.dimensions();
+    let caption_height = options
+        .caption
+        .as_ref()
+        .map_or(0.0, |_| CAPTION_LEADING * options.caption_size);
+    let drawing_height = height - 2.0 * options.margin - caption_height;
+    let side = (width - 2.0 * options.margin)
+        .min(drawing_height)
+        .max(MIN_SIDE);
+    let scale = side / (2.0 * size);
+    let centre = Vector2::new(
+        width / 2.0,
+        options.margin + caption_height + drawing_height / 2.0,
+    );
+
+    let mut content = Vec::new();
+    if let Some(background) = options.background {
+        let (red, green, blue) = channels(background);
+        let _ = writeln!(
+            content,
+            "{red:.3} {green:.3} {blue:.3} rg 0 0 {width:.2} {height:.2} re f"
+        );
+    }
+    let _ = writeln!(
+        content,
+        "q\n{:.5} 0 0 {:.5} {:.3} {:.3} cm\n1 J 1 j\n{:.5} w",
+        scale,
+        scale,
+        centre.x,
+        centre.y,
+        options.stroke_width / scale
+    );
+
+    let mut current_colour = None;
+    shapes.iter().for_each(|&(shape, colour)| {
+        if current_colour != Some(colour) {
+            let (red, green, blue) = channels(colour);
+            let _ = writeln!(
+                content,
+                "{red:.3} {green:.3} {blue:.3} RG {red:.3} {green:.3} {blue:.3} rg"
+            );
+            current_colour = Some(colour);
+        }
+
+        match shape {
+            Shape::Arc {
+                centre,
+                radius,
+                start,
+                end,
+            } => {
+                arc_path(&mut content, centre, radius, start, end);
+                let _ = writeln!(content, "S");
+            }
+            Shape::Circle { centre, radius } => {
+                arc_path(&mut content, centre, radius, 0.0, 2.0 * PI);
+                let _ = writeln!(content, "h S");
+            }
+            Shape::Dot { centre } => {
+                arc_path(
+                    &mut content,
+                    centre,
+                    options.dot_radius / scale,
+                    0.0,
+                    2.0 * PI,
+                );
+                let _ = writeln!(content, "h f");
+            }
+            Shape::Segment { start, end } => {
+                let _ = writeln!(
+                    content,
+                    "{:.4} {:.4} m {:.4} {:.4} l S",
+                    start.x, start.y, end.x, end.y
+                );
+            }
+        }
+    });
+    let _ = writeln!(content, "Q");
+
+    if let Some(caption) = &options.caption {
+        let text = to_win_ansi(caption);
+        let text_width = text
+            .iter()
+            .map(|byte| match byte {
+                32..=126 => HELVETICA_WIDTHS[(byte - 32) as usize],
+                _ => DEFAULT_WIDTH,
+            } as f64)
+            .sum::<f64>()
+            * options.caption_size
+            / 1000.0;
+        let (red, green, blue) = channels(options.colour);
+
+        let _ = write!(
+            content,
+            "BT\n{red:.3} {green:.3} {blue:.3} rg\n/F1 {:.3} Tf\n{:.3} {:.3} Td\n(",
+            options.caption_size,
+            (width - text_width) / 2.0,
+            options.margin + (caption_height - 0.72 * options.caption_size) / 2.0,
+        );
+        text.iter().for_each(|byte| {
+            if matches!(byte, b'(' | b')' | b'\\') {
+                content.push(b'\\');
+            }
+            content.push(*byte);
+        });
+        let _ = writeln!(content, ") Tj\nET");
+    }
+
+    pdf_document(&content, width, height)
+}
+
+/// Appends the path of the arc going counterclockwise from `start` to `end`, as cubic Bézier
+/// curves spanning at most a quarter turn each.
+fn arc_path(content: &mut Vec<u8>, centre: Vector2, radius: f64, start: f64, end: f64) {
+    let point = |angle: f64| centre + Vector2::new(angle.cos(), angle.sin()) * radius;
+    let tangent = |angle: f64| Vector2::new(-angle.sin(), angle.cos()) * radius;
+    let curves = ((end - start) / FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = (end - start) / curves as f64;
+    let handle = 4.0 / 3.0 * (step / 4.0).tan();
+    let first = point(start);
+
+    let _ = writeln!(content, "{:.4} {:.4} m", first.x, first.y);
+    (0..curves).for_each(|curve| {
+        let from = start + curve as f64 * step;
+        let to = from + step;
+        let control1 = point(from) + tangent(from) * handle;
+        let control2 = point(to) - tangent(to) * handle;
+        let last = point(to);
+
+        let _ = writeln!(
+            content,
+            "{:.4} {:.4} {:.4} {:.4} {:.4} {:.4} c",
+            control1.x, control1.y, control2.x, control2.y, last.x, last.y
+        );
+    });
+}
+
+/// The channels of the colour, from 0 to 1.
+fn channels(colour: Colour) -> (f64, f64, f64) {
+    (
+        colour.0 as f64 / 255.0,
+        colour.1 as f64 / 255.0,
+        colour.2 as f64 / 255.0,
+    )
+}
+
+/// Encodes the text for a standard PDF font, replacing the characters it cannot draw with `?`.
+fn to_win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|character| match character as u32 {
+            code @ (32..=126 | 160..=255) => code as u8,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// Wraps the content stream of a single page `width` by `height` points into a PDF document.
+fn pdf_document(content: &[u8], width: f64, height: f64) -> Vec<u8> {
+    let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+    stream.extend_from_slice(content);
+    stream.extend_from_slice(b"endstream");
+
+    let objects = [
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_vec(),
+        format!(
+            concat!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.2} {:.2}] ",
+                "/Resources << /Font << /F1 5 0 R >> >> /Contents 4 0 R >>"
+            ),
+            width, height
+        )
+        .into_bytes(),
+        stream,
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+    ];
+
+    let mut document = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let offsets = objects
+        .iter()
+        .enumerate()
+        .map(|(index, object)| {
+            let offset = document.len();
+            let _ = writeln!(document, "{} 0 obj", index + 1);
+            document.extend_from_slice(object);
+            document.extend_from_slice(b"\nendobj\n");
+            offset
+        })
+        .collect::<Vec<usize>>();
+
+    let xref = document.len();
+    let _ = write!(
+        document,
+        "xref\n0 {}\n0000000000 65535 f \n",
+        objects.len() + 1
+    );
+    offsets.iter().for_each(|offset| {
+        let _ = writeln!(document, "{offset:010} 00000 n ");
+    });
+    let _ = write!(
+        document,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    );
+
+    document
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_without_room_has_finite_operands() {
+        let options = PdfOptions {
+            paper: Paper::Custom {
+                width: 100.0,
+                height: 100.0,
+            },
+            caption: Some(String::from("Doctor")),
+            ..PdfOptions::default()
+        };
+        let shapes = [Shape::Circle {
+            centre: Vector2::new(0.0, 0.0),
+            radius: 1.0,
+        }];
+        let pdf = String::from_utf8_lossy(&shapes_to_pdf(&shapes, 2.0, &options)).into_owned();
+
+        assert!(!pdf.contains("inf") && !pdf.contains("NaN"));
+    }
+
+    #[test]
+    fn background_fills_the_page() {
+        let options = PdfOptions {
+            paper: Paper::Letter,
+            background: Some(Colour(0xff, 0xff, 0x00)),
+            ..PdfOptions::default()
+        };
+        let pdf = String::from_utf8_lossy(&shapes_to_pdf(&[], 1.0, &options)).into_owned();
+
+        assert!(pdf.contains("1.000 1.000 0.000 rg 0 0 612.00 792.00 re f"));
+    }
+}
@@ -0,0 +1,359 @@
+use crate::letters::*;
+use crate::numbers::*;
+use crate::sentences::*;
+use crate::shapes::Shape;
+use geomath::prelude::coordinates::Polar;
+use geomath::prelude::Metric;
+use geomath::vector::Vector2;
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+const MARGIN: f64 = 1.0;
+/// How close, in millimetres, the pen has to be to the start of the next stroke to draw on
+/// without lifting.
+const JOIN_TOLERANCE: f64 = 0.01;
+/// The number of HPGL plotter units in a millimetre.
+const HPGL_UNITS: f64 = 40.0;
+/// The number of straight steps in each turn of a dot spiral.
+const SPIRAL_STEPS: usize = 16;
+
+/// How the dots of a drawing are plotted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DotStyle {
+    /// Lowers the pen onto the dot and holds it there for `dwell` seconds. HPGL has no dwell.
+    Plunge { dwell: f64 },
+    /// Draws a spiral out from the dot, `radius` millimetres wide and turning `turns` times.
+    Spiral { radius: f64, turns: f64 },
+}
+
+/// The machine settings of a plot. Lengths are in millimetres.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotterOptions {
+    /// The width and height of the drawing, which has its lower left corner on the origin.
+    pub size: f64,
+    /// The speed of the pen while drawing, in millimetres per minute.
+    pub feed_rate: f64,
+    /// The G-code lowering the pen or switching the laser on.
+    pub pen_down: String,
+    /// The G-code raising the pen or switching the laser off.
+    pub pen_up: String,
+    pub dots: DotStyle,
+}
+
+impl Default for PlotterOptions {
+    fn default() -> Self {
+        PlotterOptions {
+            size: 100.0,
+            feed_rate: 1000.0,
+            pen_down: String::from("G1 Z0"),
+            pen_up: String::from("G0 Z5"),
+            dots: DotStyle::Spiral {
+                radius: 0.5,
+                turns: 2.0,
+            },
+        }
+    }
+}
+
+/// A stroke of the plot, in millimetres and in the direction the pen draws it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Stroke {
+    Line {
+        start: Vector2,
+        end: Vector2,
+    },
+    /// The arc around `centre` from the angle `start`, turning `sweep` radians counterclockwise,
+    /// or clockwise when negative.
+    Arc {
+        centre: Vector2,
+        radius: f64,
+        start: f64,
+        sweep: f64,
+    },
+    Dot(Vector2),
+}
+
+impl Stroke {
+    fn start(&self) -> Vector2 {
+        match *self {
+            Stroke::Line { start, .. } => start,
+            Stroke::Arc {
+                centre,
+                radius,
+                start,
+                ..
+            } => centre + Vector2::from_polar(radius, start),
+            Stroke::Dot(centre) => centre,
+        }
+    }
+
+    fn end(&self) -> Vector2 {
+        match *self {
+            Stroke::Line { end, .. } => end,
+            Stroke::Arc {
+                centre,
+                radius,
+                start,
+                sweep,
+            } => centre + Vector2::from_polar(radius, start + sweep),
+            Stroke::Dot(centre) => centre,
+        }
+    }
+}
+
+/// Writes a G-code program plotting the word, centred on its word circle.
+pub fn word_to_gcode(word: &GallifreyanWord, options: &PlotterOptions) -> String {
+    shapes_to_gcode(&word.to_shapes(), word.outer_size() + MARGIN, options)
+}
+
+/// Writes a G-code program plotting the sentence, centred on its sentence circle.
+pub fn sentence_to_gcode(sentence: &GallifreyanSentence, options: &PlotterOptions) -> String {
+    shapes_to_gcode(&sentence.to_shapes(), sentence.size() + MARGIN, options)
+}
+
+/// Writes a G-code program plotting the number, centred on its number circle.
+pub fn number_to_gcode(number: &GallifreyanNumber, options: &PlotterOptions) -> String {
+    shapes_to_gcode(&number.to_shapes(), number.size() + 3.0 * MARGIN, options)
+}
+
+/// Writes a G-code program plotting the shapes, scaling the square spanning `size` around the
+/// origin to `options.size` millimetres. Arcs and circles are drawn with G2 and G3 moves, and the
+/// pen is only lifted to travel between strokes that do not meet.
+pub fn shapes_to_gcode(shapes: &[Shape], size: f64, options: &PlotterOptions) -> String {
+    let mut gcode = String::new();
+    let mut pen = None;
+    let _ = writeln!(
+        gcode,
+        "G21\nG90\n{}\nF{:.1}",
+        options.pen_up, options.feed_rate
+    );
+
+    plot_order(shapes, size, options.size)
+        .into_iter()
+        .for_each(|stroke| {
+            let start = stroke.start();
+
+            if pen.is_none_or(|pen: Vector2| pen.distance(&start) > JOIN_TOLERANCE) {
+                if pen.is_some() {
+                    let _ = writeln!(gcode, "{}", options.pen_up);
+                }
+                let _ = writeln!(
+                    gcode,
+                    "G0 X{:.3} Y{:.3}\n{}",
+                    start.x, start.y, options.pen_down
+                );
+            }
+
+            match stroke {
+                Stroke::Line { end, .. } => {
+                    let _ = writeln!(gcode, "G1 X{:.3} Y{:.3}", end.x, end.y);
+                }
+                Stroke::Arc {
+                    centre,
+                    radius,
+                    start,
+                    sweep,
+                } => {
+                    // Full circles are ambiguous to many controllers, so arcs are drawn in halves.
+                    let pieces = (sweep.abs() / PI).ceil().max(1.0) as usize;
+                    (0..pieces).for_each(|piece| {
+                        let from = start + sweep * piece as f64 / pieces as f64;
+                        let to = start + sweep * (piece + 1) as f64 / pieces as f64;
+                        let offset = Vector2::from_polar(radius, from);
+                        let end = centre + Vector2::from_polar(radius, to);
+
+                        let _ = writeln!(
+                            gcode,
+                            "G{} X{:.3} Y{:.3} I{:.3} J{:.3}",
+                            if sweep > 0.0 { 3 } else { 2 },
+                            end.x,
+                            end.y,
+                            -offset.x,
+                            -offset.y
+                        );
+                    });
+                }
+                Stroke::Dot(centre) => match options.dots {
+                    DotStyle::Plunge { dwell } if dwell > 0.0 => {
+                        let _ = writeln!(gcode, "G4 P{dwell:.3}");
+                    }
+                    DotStyle::Plunge { .. } => {}
+                    DotStyle::Spiral { radius, turns } => {
+                        spiral(centre, radius, turns).iter().for_each(|point| {
+                            let _ = writeln!(gcode, "G1 X{:.3} Y{:.3}", point.x, point.y);
+                        });
+                    }
+                },
+            }
+
+            // A dot is never joined to the next stroke, so the pen lifts straight after it.
+            pen = match stroke {
+                Stroke::Dot(_) => {
+                    let _ = writeln!(gcode, "{}", options.pen_up);
+                    None
+                }
+                _ => Some(stroke.end()),
+            };
+        });
+
+    if pen.is_some() {
+        let _ = writeln!(gcode, "{}", options.pen_up);
+    }
+    let _ = writeln!(gcode, "G0 X0 Y0\nM2");
+
+    gcode
+}
+
+/// Writes an HPGL program plotting the word, centred on its word circle.
+pub fn word_to_hpgl(word: &GallifreyanWord, options: &PlotterOptions) -> String {
+    shapes_to_hpgl(&word.to_shapes(), word.outer_size() + MARGIN, options)
+}
+
+/// Writes an HPGL program plotting the sentence, centred on its sentence circle.
+pub fn sentence_to_hpgl(sentence: &GallifreyanSentence, options: &PlotterOptions) -> String {
+    shapes_to_hpgl(&sentence.to_shapes(), sentence.size() + MARGIN, options)
+}
+
+/// Writes an HPGL program plotting the number, centred on its number circle.
+pub fn number_to_hpgl(number: &GallifreyanNumber, options: &PlotterOptions) -> String {
+    shapes_to_hpgl(&number.to_shapes(), number.size() + 3.0 * MARGIN, options)
+}
+
+/// Writes an HPGL program plotting the shapes like `shapes_to_gcode`, with pen 1 and AA arcs. The
+/// pen commands of `options` are G-code and are not used.
+pub fn shapes_to_hpgl(shapes: &[Shape], size: f64, options: &PlotterOptions) -> String {
+    let units = |point: Vector2| {
+        format!(
+            "{},{}",
+            (point.x * HPGL_UNITS).round() as i64,
+            (point.y * HPGL_UNITS).round() as i64
+        )
+    };
+    let mut hpgl = format!("IN;SP1;VS{:.1};\n", options.feed_rate / 600.0);
+    let mut pen = None;
+
+    plot_order(shapes, size, options.size)
+        .into_iter()
+        .for_each(|stroke| {
+            let start = stroke.start();
+
+            if pen.is_none_or(|pen: Vector2| pen.distance(&start) > JOIN_TOLERANCE) {
+                let _ = writeln!(hpgl, "PU{};", units(start));
+            }
+
+            let _ = match stroke {
+                Stroke::Line { end, .. } => writeln!(hpgl, "PD{};", units(end)),
+                Stroke::Arc { centre, sweep, .. } => {
+                    writeln!(hpgl, "PD;AA{},{:.3};", units(centre), sweep.to_degrees())
+                }
+                Stroke::Dot(centre) => match options.dots {
+                    DotStyle::Plunge { .. } => writeln!(hpgl, "PD;PU;"),
+                    DotStyle::Spiral { radius, turns } => writeln!(
+                        hpgl,
+                        "PD{};PU;",
+                        spiral(centre, radius, turns)
+                            .into_iter()
+                            .map(units)
+                            .collect::<Vec<String>>()
+                            .join(",")
+                    ),
+                },
+            };
+
+            pen = match stroke {
+                Stroke::Dot(_) => None,
+                _ => Some(stroke.end()),
+            };
+        });
+
+    hpgl.push_str("PU;SP0;\n");
+    hpgl
+}
+
+/// The strokes of the shapes, scaled from the square spanning `size` around the origin to a square
+/// `millimetres` wide with its lower left corner on the origin.
+///
+/// The strokes are ordered to keep pen-up travel short: starting from the origin, the pen always
+/// draws next the stroke with the nearest start, drawing segments and arcs from either end and
+/// circles from their nearest point.
+fn plot_order(shapes: &[Shape], size: f64, millimetres: f64) -> Vec<Stroke> {
+    let scale = millimetres / (2.0 * size);
+    let corner = Vector2::new(size, size);
+    let to_plot = |point: Vector2| (point + corner) * scale;
+    let mut remaining = shapes.to_vec();
+    let mut pen = Vector2::new(0.0, 0.0);
+    let mut strokes = Vec::new();
+
+    while !remaining.is_empty() {
+        let (index, stroke) = remaining
+            .iter()
+            .enumerate()
+            .flat_map(|(index, shape)| {
+                let ways = match *shape {
+                    Shape::Arc {
+                        centre,
+                        radius,
+                        start,
+                        end,
+                    } => vec![
+                        Stroke::Arc {
+                            centre: to_plot(centre),
+                            radius: radius * scale,
+                            start,
+                            sweep: end - start,
+                        },
+                        Stroke::Arc {
+                            centre: to_plot(centre),
+                            radius: radius * scale,
+                            start: end,
+                            sweep: start - end,
+                        },
+                    ],
+                    Shape::Circle { centre, radius } => vec![Stroke::Arc {
+                        centre: to_plot(centre),
+                        radius: radius * scale,
+                        start: (pen - to_plot(centre)).phi(),
+                        sweep: 2.0 * PI,
+                    }],
+                    Shape::Dot { centre } => vec![Stroke::Dot(to_plot(centre))],
+                    Shape::Segment { start, end } => vec![
+                        Stroke::Line {
+                            start: to_plot(start),
+                            end: to_plot(end),
+                        },
+                        Stroke::Line {
+                            start: to_plot(end),
+                            end: to_plot(start),
+                        },
+                    ],
+                };
+
+                ways.into_iter().map(move |stroke| (index, stroke))
+            })
+            .min_by(|(_, stroke1), (_, stroke2)| {
+                stroke1
+                    .start()
+                    .distance(&pen)
+                    .total_cmp(&stroke2.start().distance(&pen))
+            })
+            .expect("There should be a stroke left to draw.");
+
+        remaining.swap_remove(index);
+        pen = stroke.end();
+        strokes.push(stroke);
+    }
+
+    strokes
+}
+
+/// The points of a spiral turning out from `centre` to `radius`, after the centre itself.
+fn spiral(centre: Vector2, radius: f64, turns: f64) -> Vec<Vector2> {
+    let steps = (turns * SPIRAL_STEPS as f64).ceil().max(1.0) as usize;
+
+    (1..=steps)
+        .map(|step| {
+            let along = step as f64 / steps as f64;
+            centre + Vector2::from_polar(radius * along, 2.0 * PI * turns * along)
+        })
+        .collect()
+}
@@ -0,0 +1,736 @@
+use crate::glyphs::Style;
+use crate::recognition::*;
+use crate::shapes::Shape;
+use core::fmt;
+use geomath::vector::Vector2;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt::Display;
+
+/// How different from the background, summed over the colour channels, a pixel must be to be ink.
+const INK_THRESHOLD: u32 = 96;
+/// The largest number of cells along each axis of the circle search.
+const SEARCH_RESOLUTION: usize = 256;
+/// The most circles the search proposes before checking them against the ink.
+const MAX_CANDIDATES: usize = 256;
+/// The number of angular sectors used to measure how much of a circle is drawn.
+const COVERAGE_SECTORS: usize = 72;
+/// The smallest share of its sectors a circle must have ink in to be read.
+const MIN_COVERAGE: f64 = 0.25;
+/// How many stroke widths a straight stroke must run along a circle for to be told apart from it.
+const MIN_STRAIGHT_WIDTHS: f64 = 5.0;
+/// How many sectors can be missing from a full circle, where other strokes cross it.
+const MAX_GAP: usize = 2;
+/// How many stroke widths of a full circle can be missing, where other strokes join it.
+const MAX_GAP_WIDTHS: f64 = 3.0;
+/// The smallest cosine between the normal of a stroke and the radius of a circle it is drawn on.
+const MIN_ALIGNMENT: f64 = 0.9;
+/// The radius in pixels of the neighbourhood used to find the direction of a stroke.
+const DIRECTION_RADIUS: isize = 3;
+
+/// The error returned when an image cannot be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseRasterError {
+    reason: String,
+}
+
+impl ParseRasterError {
+    /// What could not be read.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl Display for ParseRasterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl Error for ParseRasterError {}
+
+/// Reads the word drawn in a PNG image, expecting the proportions of `style`.
+pub fn png_to_word(png: &[u8], style: &Style) -> Result<RecognisedWord, ParseRasterError> {
+    recognise_word(&png_to_shapes(png)?, style).ok_or_else(|| ParseRasterError {
+        reason: String::from("no word circle"),
+    })
+}
+
+/// Reads the circles, arcs, lines and dots drawn in a PNG image, in pixels with the y axis
+/// pointing up. Ink is whatever differs from the most common colour of the image border.
+pub fn png_to_shapes(png: &[u8]) -> Result<Vec<Shape>, ParseRasterError> {
+    let decoding_error = |error: png::DecodingError| ParseRasterError {
+        reason: error.to_string(),
+    };
+    let mut decoder = png::Decoder::new(png);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(decoding_error)?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(decoding_error)?;
+
+    let pixels = buffer[..info.buffer_size()]
+        .chunks_exact(info.color_type.samples())
+        .map(|pixel| match *pixel {
+            [grey] => [grey, grey, grey, 255],
+            [grey, alpha] => [grey, grey, grey, alpha],
+            [red, green, blue] => [red, green, blue, 255],
+            [red, green, blue, alpha] => [red, green, blue, alpha],
+            _ => unreachable!("PNG pixels have one to four samples."),
+        })
+        .collect::<Vec<[u8; 4]>>();
+    let width = info.width as usize;
+    let height = info.height as usize;
+
+    let mut border_colours = HashMap::new();
+    (0..width)
+        .flat_map(|x| [(x, 0), (x, height - 1)])
+        .chain((0..height).flat_map(|y| [(0, y), (width - 1, y)]))
+        .for_each(|(x, y)| *border_colours.entry(pixels[y * width + x]).or_insert(0) += 1);
+    let background = border_colours
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map_or([255; 4], |(colour, _)| colour);
+
+    let ink = pixels
+        .iter()
+        .map(|pixel| {
+            let difference = (0..3)
+                .map(|channel| pixel[channel].abs_diff(background[channel]) as u32)
+                .sum::<u32>();
+            difference * pixel[3] as u32 / 255 > INK_THRESHOLD
+        })
+        .collect::<Vec<bool>>();
+
+    Ok(bitmap_to_shapes(&ink, width))
+}
+
+/// Reads the circles, arcs, lines and dots of a bitmap of `width` pixels, where `ink` is set for
+/// every drawn pixel, row by row from the top. The shapes are in pixels with the y axis pointing
+/// up.
+///
+/// Dots are the blobs left after eroding the strokes away. Circles are found by letting every
+/// stroke pixel vote for the centres along its normal, then fitted to the pixels near each
+/// candidate. The pixels left over are read as lines.
+///
+/// Dots must be clearly thicker than the strokes and clear of them, and the lines on vowels a few
+/// pixels longer than the strokes are wide, so small or crowded drawings can lose them. There are
+/// no shapes when `ink` does not split into whole rows of `width` pixels.
+pub fn bitmap_to_shapes(ink: &[bool], width: usize) -> Vec<Shape> {
+    if width == 0 || !ink.len().is_multiple_of(width) {
+        return Vec::new();
+    }
+
+    let mut bitmap = Bitmap {
+        ink: ink.to_vec(),
+        width,
+        height: ink.len() / width,
+    };
+    let stroke_width = bitmap.stroke_width();
+
+    let dots = bitmap.take_dots(stroke_width);
+    let pixels = bitmap.pixels();
+    let (mut shapes, claimed) = find_circles(&bitmap, &pixels, stroke_width);
+
+    for (x, y) in dots {
+        shapes.push(Shape::Dot {
+            centre: Vector2::new(x, -y),
+        });
+    }
+    let mut lines = find_lines(&bitmap, &pixels, &claimed, &shapes, stroke_width);
+    shapes.append(&mut lines);
+
+    shapes
+}
+
+struct Bitmap {
+    ink: Vec<bool>,
+    width: usize,
+    height: usize,
+}
+
+impl Bitmap {
+    fn get(&self, x: isize, y: isize) -> bool {
+        (0..self.width as isize).contains(&x)
+            && (0..self.height as isize).contains(&y)
+            && self.ink[y as usize * self.width + x as usize]
+    }
+
+    /// The ink pixels, as the coordinates of their centres.
+    fn pixels(&self) -> Vec<(f64, f64)> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.ink[y * self.width + x])
+            .map(|(x, y)| (x as f64, y as f64))
+            .collect()
+    }
+
+    /// The median thickness of the strokes, measured as the shortest horizontal, vertical or
+    /// diagonal run of ink through each pixel.
+    fn stroke_width(&self) -> f64 {
+        let run = |x: isize, y: isize, dx: isize, dy: isize| {
+            let length = |sign: isize| {
+                (1..)
+                    .take_while(|step| self.get(x + sign * step * dx, y + sign * step * dy))
+                    .count()
+            };
+            1 + length(1) + length(-1)
+        };
+        let mut runs = self
+            .pixels()
+            .iter()
+            .map(|&(x, y)| {
+                [(1, 0), (0, 1), (1, 1), (1, -1)]
+                    .into_iter()
+                    .map(|(dx, dy)| run(x as isize, y as isize, dx, dy))
+                    .min()
+                    .unwrap_or(1)
+            })
+            .collect::<Vec<usize>>();
+        runs.sort_unstable();
+
+        runs.get(runs.len() / 2).map_or(1.0, |&run| run as f64)
+    }
+
+    /// Removes the dots from the bitmap, returning their centres. Dots are the blobs thicker than
+    /// the strokes with no other ink around them.
+    fn take_dots(&mut self, stroke_width: f64) -> Vec<(f64, f64)> {
+        let erosion = stroke_width.round().max(1.0) as isize;
+        let eroded = Bitmap {
+            ink: (0..self.height as isize)
+                .flat_map(|y| (0..self.width as isize).map(move |x| (x, y)))
+                .map(|(x, y)| {
+                    (-erosion..=erosion)
+                        .all(|dy| (-erosion..=erosion).all(|dx| self.get(x + dx, y + dy)))
+                })
+                .collect(),
+            width: self.width,
+            height: self.height,
+        };
+
+        let dots = eroded
+            .components(&vec![false; self.width * self.height])
+            .into_iter()
+            .filter(|component| {
+                let (x_range, y_range) = extent(component);
+                x_range.max(y_range) <= 6.0 * stroke_width
+            })
+            .map(|component| {
+                let count = component.len() as f64;
+                let centre = component.iter().fold((0.0, 0.0), |sum, pixel| {
+                    (sum.0 + pixel.0 / count, sum.1 + pixel.1 / count)
+                });
+                let reach = component.iter().fold(0.0_f64, |reach, pixel| {
+                    reach.max((pixel.0 - centre.0).hypot(pixel.1 - centre.1))
+                });
+
+                (centre, reach + erosion as f64 + 0.5)
+            })
+            .filter(|&(centre, radius)| {
+                let reach = (radius + 2.0).ceil() as isize;
+                (-reach..=reach).all(|dy| {
+                    (-reach..=reach).all(|dx| {
+                        let (x, y) = (
+                            centre.0.round() as isize + dx,
+                            centre.1.round() as isize + dy,
+                        );
+                        let distance = (x as f64 - centre.0).hypot(y as f64 - centre.1);
+                        !(radius + 0.5..=radius + 1.5).contains(&distance) || !self.get(x, y)
+                    })
+                })
+            })
+            .collect::<Vec<((f64, f64), f64)>>();
+
+        dots.iter().for_each(|&((x, y), radius)| {
+            let reach = (radius + 0.5).ceil() as isize;
+            (-reach..=reach).for_each(|dy| {
+                (-reach..=reach).for_each(|dx| {
+                    let (px, py) = (x.round() as isize + dx, y.round() as isize + dy);
+                    if self.get(px, py) && (px as f64 - x).hypot(py as f64 - y) <= radius + 0.5 {
+                        self.ink[py as usize * self.width + px as usize] = false;
+                    }
+                })
+            })
+        });
+
+        dots.into_iter().map(|(centre, _)| centre).collect()
+    }
+
+    /// The 8-connected groups of ink pixels, skipping the pixels set in `excluded`.
+    fn components(&self, excluded: &[bool]) -> Vec<Vec<(f64, f64)>> {
+        let mut visited = excluded.to_vec();
+        let mut components = Vec::new();
+
+        for start in 0..self.ink.len() {
+            if !self.ink[start] || visited[start] {
+                continue;
+            }
+
+            let mut component = Vec::new();
+            let mut stack = vec![start];
+            visited[start] = true;
+
+            while let Some(index) = stack.pop() {
+                let (x, y) = ((index % self.width) as isize, (index / self.width) as isize);
+                component.push((x as f64, y as f64));
+
+                for (dx, dy) in NEIGHBOURS {
+                    let (nx, ny) = (x + dx, y + dy);
+                    let neighbour = ny as usize * self.width + nx as usize;
+                    if self.get(nx, ny) && !visited[neighbour] {
+                        visited[neighbour] = true;
+                        stack.push(neighbour);
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// The unit normal of the stroke through the pixel at `(x, y)`, or `None` where the ink around
+    /// it has no clear direction, such as where strokes cross.
+    fn normal(&self, x: isize, y: isize) -> Option<(f64, f64)> {
+        let neighbourhood = (-DIRECTION_RADIUS..=DIRECTION_RADIUS)
+            .flat_map(|dy| (-DIRECTION_RADIUS..=DIRECTION_RADIUS).map(move |dx| (dx, dy)))
+            .filter(|&(dx, dy)| self.get(x + dx, y + dy))
+            .map(|(dx, dy)| (dx as f64, dy as f64))
+            .collect::<Vec<(f64, f64)>>();
+        let (xx, xy, yy) = covariance(&neighbourhood);
+        let (major, minor) = eigenvalues(xx, xy, yy);
+
+        (minor < 0.3 * major).then(|| {
+            let angle = 0.5 * (2.0 * xy).atan2(xx - yy);
+            (-angle.sin(), angle.cos())
+        })
+    }
+}
+
+const NEIGHBOURS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// The width and height of the box around `pixels`.
+fn extent(pixels: &[(f64, f64)]) -> (f64, f64) {
+    let (min_x, max_x, min_y, max_y) = pixels.iter().fold(
+        (
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ),
+        |(min_x, max_x, min_y, max_y), &(x, y)| {
+            (min_x.min(x), max_x.max(x), min_y.min(y), max_y.max(y))
+        },
+    );
+
+    (max_x - min_x, max_y - min_y)
+}
+
+/// The covariance `(xx, xy, yy)` of `points` around their mean.
+fn covariance(points: &[(f64, f64)]) -> (f64, f64, f64) {
+    let count = points.len().max(1) as f64;
+    let (mean_x, mean_y) = points.iter().fold((0.0, 0.0), |sum, point| {
+        (sum.0 + point.0 / count, sum.1 + point.1 / count)
+    });
+
+    points
+        .iter()
+        .fold((0.0, 0.0, 0.0), |(xx, xy, yy), &(x, y)| {
+            let (dx, dy) = (x - mean_x, y - mean_y);
+            (
+                xx + dx * dx / count,
+                xy + dx * dy / count,
+                yy + dy * dy / count,
+            )
+        })
+}
+
+/// The larger and smaller eigenvalues of the symmetric matrix `[xx xy; xy yy]`.
+fn eigenvalues(xx: f64, xy: f64, yy: f64) -> (f64, f64) {
+    let mean = (xx + yy) / 2.0;
+    let spread = ((xx - yy) / 2.0).hypot(xy);
+
+    (mean + spread, (mean - spread).max(0.0))
+}
+
+/// The sector of the circle around `centre` that `pixel` lies in.
+fn sector(centre: (f64, f64), pixel: (f64, f64)) -> usize {
+    let angle = (pixel.1 - centre.1)
+        .atan2(pixel.0 - centre.0)
+        .rem_euclid(2.0 * PI);
+    ((angle / (2.0 * PI) * COVERAGE_SECTORS as f64) as usize).min(COVERAGE_SECTORS - 1)
+}
+
+/// Finds the circles and arcs drawn through `pixels`, returning them with the pixels they claim.
+fn find_circles(
+    bitmap: &Bitmap,
+    pixels: &[(f64, f64)],
+    stroke_width: f64,
+) -> (Vec<Shape>, Vec<bool>) {
+    let cell = (bitmap.width.max(bitmap.height) as f64 / SEARCH_RESOLUTION as f64).max(1.0);
+    let columns = (bitmap.width as f64 / cell).ceil() as usize;
+    let rows = (bitmap.height as f64 / cell).ceil() as usize;
+    let min_radius = (3.0 * cell).max(2.0 * stroke_width + 2.0);
+    let radii =
+        ((bitmap.width.min(bitmap.height) as f64 / 2.0 - min_radius) / cell).max(0.0) as usize;
+    let index = |column: usize, row: usize, radius: usize| (radius * rows + row) * columns + column;
+
+    let normals = pixels
+        .iter()
+        .map(|&(x, y)| bitmap.normal(x as isize, y as isize))
+        .collect::<Vec<Option<(f64, f64)>>>();
+
+    let mut votes = vec![0u16; columns * rows * radii];
+    pixels.iter().zip(&normals).for_each(|(&(x, y), normal)| {
+        let Some((nx, ny)) = *normal else {
+            return;
+        };
+        (0..radii).for_each(|radius_index| {
+            let radius = min_radius + radius_index as f64 * cell;
+            [1.0, -1.0].into_iter().for_each(|sign| {
+                let (cx, cy) = (x + sign * radius * nx, y + sign * radius * ny);
+                if cx >= 0.0 && cy >= 0.0 {
+                    let (column, row) = ((cx / cell) as usize, (cy / cell) as usize);
+                    if column < columns && row < rows {
+                        let vote = &mut votes[index(column, row, radius_index)];
+                        *vote = vote.saturating_add(1);
+                    }
+                }
+            });
+        });
+    });
+
+    let min_votes = (2.0 * PI * min_radius * MIN_COVERAGE / cell) as u16;
+    let mut candidates = (0..radii)
+        .flat_map(|radius| {
+            (0..rows).flat_map(move |row| (0..columns).map(move |column| (column, row, radius)))
+        })
+        .filter(|&(column, row, radius)| {
+            let vote = votes[index(column, row, radius)];
+            vote >= min_votes
+                && (-1..=1).all(|dr: isize| {
+                    (-1..=1).all(|dy: isize| {
+                        (-1..=1).all(|dx: isize| {
+                            let (c, r, s) = (
+                                column as isize + dx,
+                                row as isize + dy,
+                                radius as isize + dr,
+                            );
+                            !(0..columns as isize).contains(&c)
+                                || !(0..rows as isize).contains(&r)
+                                || !(0..radii as isize).contains(&s)
+                                || votes[index(c as usize, r as usize, s as usize)] <= vote
+                        })
+                    })
+                })
+        })
+        .map(|(column, row, radius)| {
+            (
+                votes[index(column, row, radius)],
+                ((column as f64 + 0.5) * cell, (row as f64 + 0.5) * cell),
+                min_radius + radius as f64 * cell,
+            )
+        })
+        .collect::<Vec<(u16, (f64, f64), f64)>>();
+    candidates.sort_by_key(|(votes, ..)| Reverse(*votes));
+    candidates.truncate(MAX_CANDIDATES);
+
+    let mut claimed = vec![false; pixels.len()];
+    let shapes = candidates
+        .into_iter()
+        .filter_map(|(_, centre, radius)| {
+            let tolerance = cell + stroke_width;
+            let (centre, radius) = (0..3).try_fold((centre, radius), |(centre, radius), _| {
+                let near = pixels
+                    .iter()
+                    .zip(&normals)
+                    .zip(&claimed)
+                    .filter(|((&pixel, &normal), &claimed)| {
+                        !claimed && on_circle(pixel, normal, centre, radius, tolerance)
+                    })
+                    .map(|((&pixel, _), _)| pixel)
+                    .collect::<Vec<(f64, f64)>>();
+                fit_circle(&near).filter(|(_, fitted)| (fitted - radius).abs() <= 2.0 * cell)
+            })?;
+
+            let mut sectors = [false; COVERAGE_SECTORS];
+            let mut turns = vec![Vec::new(); COVERAGE_SECTORS];
+            let support = pixels
+                .iter()
+                .zip(&normals)
+                .zip(claimed.iter_mut())
+                .filter(|((&pixel, &normal), claimed)| {
+                    !**claimed && on_circle(pixel, normal, centre, radius, stroke_width + 1.0)
+                })
+                .map(|((&pixel, &normal), claimed)| {
+                    let sector = sector(centre, pixel);
+                    sectors[sector] = true;
+                    if let Some(normal) = normal {
+                        turns[sector].push(normal_turn(centre, pixel, normal));
+                    }
+                    claimed
+                })
+                .collect::<Vec<&mut bool>>();
+            // Straight lines running tangent to the circle for a while are not part of it.
+            let drawn = remove_straight_runs(&sectors, &turns, radius, stroke_width);
+            let coverage =
+                drawn.iter().filter(|&&covered| covered).count() as f64 / COVERAGE_SECTORS as f64;
+            let density = support.len() as f64 / (2.0 * PI * radius * coverage).max(1.0);
+
+            if coverage < MIN_COVERAGE || density < 0.5 {
+                return None;
+            }
+            support.into_iter().for_each(|claimed| *claimed = true);
+
+            let closing = MAX_GAP_WIDTHS * (stroke_width + 1.0);
+            Some(circle_to_shape(centre, radius, &drawn, closing))
+        })
+        .collect();
+
+    (shapes, claimed)
+}
+
+/// The position of `pixel` around `centre` and the angle from the radius through it to the
+/// normal of its stroke.
+fn normal_turn(centre: (f64, f64), pixel: (f64, f64), normal: (f64, f64)) -> (f64, f64) {
+    let (dx, dy) = (pixel.0 - centre.0, pixel.1 - centre.1);
+
+    (
+        dy.atan2(dx),
+        ((normal.1 * dx - normal.0 * dy) / (normal.0 * dx + normal.1 * dy)).atan(),
+    )
+}
+
+/// The sectors drawn by the circle, leaving out the runs of sectors drawn by a straight stroke.
+///
+/// Along a circle the normal of the stroke follows the radius, while along a straight stroke it
+/// keeps its direction and so turns away from the radius as fast as the radius turns.
+fn remove_straight_runs(
+    sectors: &[bool; COVERAGE_SECTORS],
+    turns: &[Vec<(f64, f64)>],
+    radius: f64,
+    stroke_width: f64,
+) -> [bool; COVERAGE_SECTORS] {
+    let sector_angle = 2.0 * PI / COVERAGE_SECTORS as f64;
+    let min_run = MIN_STRAIGHT_WIDTHS * (stroke_width + 1.0) / (sector_angle * radius);
+    let mut drawn = *sectors;
+    let (gap_start, _) = longest_gap(sectors);
+    let mut run: Vec<usize> = Vec::new();
+
+    (0..=COVERAGE_SECTORS)
+        .map(|offset| (gap_start + offset) % COVERAGE_SECTORS)
+        .for_each(
+            |sector| match sectors[sector] && run.len() < COVERAGE_SECTORS {
+                true => run.push(sector),
+                false => {
+                    let points = run
+                        .iter()
+                        .flat_map(|&sector| &turns[sector])
+                        .map(|&(position, turn)| {
+                            let start = run[0] as f64 * sector_angle;
+                            ((position - start).rem_euclid(2.0 * PI), turn)
+                        })
+                        .collect::<Vec<(f64, f64)>>();
+                    let (xx, xy, _) = covariance(&points);
+
+                    if run.len() as f64 >= min_run && xx > 0.0 && (xy / xx).abs() > 0.5 {
+                        run.iter().for_each(|&sector| drawn[sector] = false);
+                    }
+                    run.clear();
+                }
+            },
+        );
+
+    drawn
+}
+
+/// Whether `pixel` lies within `tolerance` of the circle, along a stroke tangent to it. Pixels
+/// without a clear stroke direction are taken to be tangent.
+fn on_circle(
+    pixel: (f64, f64),
+    normal: Option<(f64, f64)>,
+    centre: (f64, f64),
+    radius: f64,
+    tolerance: f64,
+) -> bool {
+    let (dx, dy) = (pixel.0 - centre.0, pixel.1 - centre.1);
+    let distance = dx.hypot(dy);
+
+    (distance - radius).abs() <= tolerance
+        && normal.is_none_or(|(nx, ny)| (nx * dx + ny * dy).abs() >= MIN_ALIGNMENT * distance)
+}
+
+/// The least squares circle through `pixels`, if they are not all on a line.
+fn fit_circle(pixels: &[(f64, f64)]) -> Option<((f64, f64), f64)> {
+    if pixels.len() < 3 {
+        return None;
+    }
+
+    let count = pixels.len() as f64;
+    let (mean_x, mean_y) = pixels.iter().fold((0.0, 0.0), |sum, pixel| {
+        (sum.0 + pixel.0 / count, sum.1 + pixel.1 / count)
+    });
+    let (mut uu, mut uv, mut vv, mut uuu, mut vvv, mut uvv, mut vuu) =
+        (0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    pixels.iter().for_each(|&(x, y)| {
+        let (u, v) = (x - mean_x, y - mean_y);
+        uu += u * u;
+        uv += u * v;
+        vv += v * v;
+        uuu += u * u * u;
+        vvv += v * v * v;
+        uvv += u * v * v;
+        vuu += v * u * u;
+    });
+
+    let determinant = uu * vv - uv * uv;
+    if determinant.abs() < 1e-9 {
+        return None;
+    }
+
+    let (bu, bv) = ((uuu + uvv) / 2.0, (vvv + vuu) / 2.0);
+    let (u, v) = (
+        (bu * vv - bv * uv) / determinant,
+        (bv * uu - bu * uv) / determinant,
+    );
+
+    Some((
+        (u + mean_x, v + mean_y),
+        (u * u + v * v + (uu + vv) / count).sqrt(),
+    ))
+}
+
+/// The longest run of empty sectors, as its first sector and length.
+fn longest_gap(sectors: &[bool; COVERAGE_SECTORS]) -> (usize, usize) {
+    (0..COVERAGE_SECTORS)
+        .filter(|&start| {
+            !sectors[start] && sectors[(start + COVERAGE_SECTORS - 1) % COVERAGE_SECTORS]
+        })
+        .map(|start| {
+            let length = (0..COVERAGE_SECTORS)
+                .take_while(|offset| !sectors[(start + offset) % COVERAGE_SECTORS])
+                .count();
+            (start, length)
+        })
+        .max_by_key(|&(_, length)| length)
+        .unwrap_or((0, 0))
+}
+
+/// The circle, or the arc over its longest drawn stretch, with the y axis flipped to point up.
+///
+/// Gaps no longer than `closing` pixels are taken to be where other strokes join the circle.
+fn circle_to_shape(
+    centre: (f64, f64),
+    radius: f64,
+    sectors: &[bool; COVERAGE_SECTORS],
+    closing: f64,
+) -> Shape {
+    let centre_up = Vector2::new(centre.0, -centre.1);
+    let sector_angle = 2.0 * PI / COVERAGE_SECTORS as f64;
+    let (gap_start, gap_length) = longest_gap(sectors);
+
+    match gap_length <= MAX_GAP || gap_length as f64 * sector_angle * radius <= closing {
+        true => Shape::Circle {
+            centre: centre_up,
+            radius,
+        },
+        false => {
+            // Sectors are counted in image angles, which turn clockwise once y points up.
+            let drawn_start = (gap_start + gap_length) as f64 * sector_angle;
+            let drawn_end = gap_start as f64 * sector_angle;
+            Shape::arc(centre_up, radius, -drawn_end, -drawn_start)
+        }
+    }
+}
+
+/// Reads the ink not claimed by circles as lines, fitting a segment to each stroke.
+fn find_lines(
+    bitmap: &Bitmap,
+    pixels: &[(f64, f64)],
+    claimed: &[bool],
+    circles: &[Shape],
+    stroke_width: f64,
+) -> Vec<Shape> {
+    let mut excluded = vec![false; bitmap.ink.len()];
+    pixels.iter().zip(claimed).for_each(|(&(x, y), &claimed)| {
+        excluded[y as usize * bitmap.width + x as usize] = claimed;
+    });
+
+    bitmap
+        .components(&excluded)
+        .into_iter()
+        .filter_map(|component| {
+            let (xx, xy, yy) = covariance(&component);
+            let (major, minor) = eigenvalues(xx, xy, yy);
+            let angle = 0.5 * (2.0 * xy).atan2(xx - yy);
+            let direction = (angle.cos(), angle.sin());
+            let count = component.len() as f64;
+            let mean = component.iter().fold((0.0, 0.0), |sum, pixel| {
+                (sum.0 + pixel.0 / count, sum.1 + pixel.1 / count)
+            });
+            let (low, high) = component.iter().fold(
+                (f64::INFINITY, f64::NEG_INFINITY),
+                |(low, high), &(x, y)| {
+                    let projection = (x - mean.0) * direction.0 + (y - mean.1) * direction.1;
+                    (low.min(projection), high.max(projection))
+                },
+            );
+
+            // Ends cut short where the line meets a circle reach back over the circle's pixels.
+            let trimmed = stroke_width + 1.0;
+            let end = |projection: f64| {
+                (
+                    mean.0 + projection * direction.0,
+                    mean.1 + projection * direction.1,
+                )
+            };
+            let meets_circle = |(x, y): (f64, f64)| {
+                let reach = (trimmed + 1.0).ceil() as isize;
+                (-reach..=reach).any(|dy| {
+                    (-reach..=reach).any(|dx| {
+                        let (px, py) = (x.round() as isize + dx, y.round() as isize + dy);
+                        bitmap.get(px, py) && excluded[py as usize * bitmap.width + px as usize]
+                    })
+                })
+            };
+            // What is left of a circle where other strokes cross it runs along the circle.
+            let on_circle = |(x, y): (f64, f64), centre: Vector2, radius: f64| {
+                ((x - centre.x).hypot(-y - centre.y) - radius).abs() <= trimmed + 1.0
+            };
+            let is_fragment = circles.iter().any(|circle| match *circle {
+                Shape::Arc { centre, radius, .. } | Shape::Circle { centre, radius } => {
+                    on_circle(end(low), centre, radius) && on_circle(end(high), centre, radius)
+                }
+                _ => false,
+            });
+            let is_line = high - low >= stroke_width + 2.0
+                && minor.sqrt() <= 0.5 * stroke_width.max(1.0) + 0.5
+                && minor < 0.35 * major
+                && !is_fragment;
+            let low = match meets_circle(end(low)) {
+                true => low - trimmed,
+                false => low,
+            };
+            let high = match meets_circle(end(high)) {
+                true => high + trimmed,
+                false => high,
+            };
+
+            is_line.then(|| Shape::Segment {
+                start: Vector2::new(end(low).0, -end(low).1),
+                end: Vector2::new(end(high).0, -end(high).1),
+            })
+        })
+        .collect()
+}
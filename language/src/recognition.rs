@@ -0,0 +1,474 @@
+use crate::glyphs::*;
+use crate::letters::*;
+use crate::shapes::Shape;
+use geomath::prelude::coordinates::Polar;
+use geomath::prelude::Metric;
+use geomath::vector::Vector2;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// How far apart, relative to the word size, two circles can be and still be the same circle.
+const CIRCLE_TOLERANCE: f64 = 0.05;
+/// How far, in letter sizes, a base can be from where its letter is drawn.
+const PLACEMENT_TOLERANCE: f64 = 0.25;
+/// How far, relative to a stroke's radius, a line can start from the stroke it belongs to.
+const LINE_TOLERANCE: f64 = 0.15;
+/// How far, relative to a stroke's radius, a dot can be from the stroke it belongs to.
+const DOT_TOLERANCE: f64 = 0.6;
+/// How far, relative to the outer stroke, the inner stroke of a doubled letter can be from where
+/// it is drawn.
+const DOUBLED_TOLERANCE: f64 = 0.15;
+/// The confidence kept for each dot or line missing from, or added to, a letter.
+const MODIFIER_PENALTY: f64 = 0.5;
+
+/// A letter read from a drawing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecognisedLetter {
+    pub letter: GallifreyanLetter,
+    /// How closely the drawing matches the letter, from 0 to 1.
+    pub confidence: f64,
+}
+
+/// The best guess at the word of a drawing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecognisedWord {
+    letters: Vec<RecognisedLetter>,
+    centre: Vector2,
+    size: f64,
+}
+
+impl RecognisedWord {
+    /// The letters read, in reading order.
+    pub fn letters(&self) -> &[RecognisedLetter] {
+        &self.letters
+    }
+
+    /// The confidence of the least certain letter, or 1 when there are no letters.
+    pub fn confidence(&self) -> f64 {
+        self.letters
+            .iter()
+            .map(|letter| letter.confidence)
+            .fold(1.0, f64::min)
+    }
+
+    /// The centre of the word circle in the drawing.
+    pub fn centre(&self) -> Vector2 {
+        self.centre
+    }
+
+    /// The radius of the word circle in the drawing.
+    pub fn size(&self) -> f64 {
+        self.size
+    }
+
+    pub fn to_word(&self) -> GallifreyanWord {
+        GallifreyanWord::new(self.letters.iter().map(|letter| letter.letter).collect())
+    }
+}
+
+/// A circle or arc of a letter, relative to the centre of the word circle.
+struct Stroke {
+    centre: Vector2,
+    radius: f64,
+    is_arc: bool,
+    doubled: bool,
+}
+
+/// A stroke with the dots and lines attached to it.
+struct Glyph {
+    stroke: Stroke,
+    dots: usize,
+    lines: Vec<(Vector2, Vector2)>,
+}
+
+impl Glyph {
+    fn angle(&self) -> f64 {
+        self.stroke.centre.phi()
+    }
+}
+
+/// Reads the word drawn by `shapes`, expecting the proportions of `style`.
+///
+/// The word circle is the circle enclosing the most letters, or the smallest of those, so an
+/// enclosing sentence circle is skipped. Every other circle and arc is classified as a consonant
+/// or vowel base by its size and its position relative to the word circle, and the dots and lines
+/// starting on it give its modifier. Returns `None` when there is no circle to read.
+pub fn recognise_word(shapes: &[Shape], style: &Style) -> Option<RecognisedWord> {
+    let mut strokes = Vec::new();
+    let mut dots = Vec::new();
+    let mut segments = Vec::new();
+
+    shapes.iter().for_each(|shape| match *shape {
+        Shape::Arc { centre, radius, .. } => strokes.push(Stroke {
+            centre,
+            radius,
+            is_arc: true,
+            doubled: false,
+        }),
+        Shape::Circle { centre, radius } => strokes.push(Stroke {
+            centre,
+            radius,
+            is_arc: false,
+            doubled: false,
+        }),
+        Shape::Dot { centre } => dots.push(centre),
+        Shape::Segment { start, end } => segments.push((start, end)),
+    });
+
+    // Vowels drawn outside of the word circle still lie within this many of their radii of it.
+    let reach = style.moon_base_ratio / style.vowel_ratio + 0.5;
+    let enclosed = |circle: &Stroke| {
+        let tolerance = CIRCLE_TOLERANCE * circle.radius;
+
+        strokes
+            .iter()
+            .filter(|stroke| {
+                let distance = stroke.centre.distance(&circle.centre);
+                stroke.radius < circle.radius
+                    && distance > tolerance
+                    && distance < circle.radius + reach * stroke.radius
+            })
+            .fold(Vec::new(), |mut centres: Vec<Vector2>, stroke| {
+                if centres
+                    .iter()
+                    .all(|centre| centre.distance(&stroke.centre) > tolerance)
+                {
+                    centres.push(stroke.centre);
+                }
+                centres
+            })
+            .len()
+    };
+    let word_circle = strokes.iter().max_by(|circle1, circle2| {
+        enclosed(circle1)
+            .cmp(&enclosed(circle2))
+            .then(circle2.radius.total_cmp(&circle1.radius))
+    })?;
+    let (centre, size) = (word_circle.centre, word_circle.radius);
+
+    let strokes = merge_doubled(
+        strokes
+            .into_iter()
+            .filter(|stroke| stroke.radius < (1.0 + CIRCLE_TOLERANCE) * size)
+            .filter(|stroke| {
+                stroke.centre.distance(&centre) > CIRCLE_TOLERANCE * size
+                    || (stroke.radius - size).abs() > CIRCLE_TOLERANCE * size
+            })
+            .map(|stroke| Stroke {
+                centre: stroke.centre - centre,
+                ..stroke
+            })
+            .collect(),
+        style,
+    );
+    let letter_size = letter_size(&strokes, size, style);
+    let (consonants, vowels): (Vec<Stroke>, Vec<Stroke>) = strokes
+        .into_iter()
+        .partition(|stroke| stroke.radius > style.vowel_ratio.sqrt() * letter_size);
+
+    let mut consonants = consonants
+        .into_iter()
+        .map(|stroke| Glyph {
+            stroke,
+            dots: 0,
+            lines: Vec::new(),
+        })
+        .collect::<Vec<Glyph>>();
+    let mut vowels = vowels
+        .into_iter()
+        .map(|stroke| Glyph {
+            stroke,
+            dots: 0,
+            lines: Vec::new(),
+        })
+        .collect::<Vec<Glyph>>();
+
+    dots.into_iter().for_each(|dot| {
+        if let Some(glyph) = nearest(&mut consonants, |glyph| {
+            let error = ((dot - centre).distance(&glyph.stroke.centre) - glyph.stroke.radius).abs()
+                / glyph.stroke.radius;
+            (error < DOT_TOLERANCE).then_some(error)
+        }) {
+            glyph.dots += 1;
+        }
+    });
+    // A line connecting two letters starts on both of them.
+    segments
+        .into_iter()
+        .flat_map(|(start, end)| [(start, end), (end, start)])
+        .for_each(|(near, far)| {
+            let (near, far) = (near - centre, far - centre);
+            let line_error = |glyph: &Glyph| {
+                let error = (near.distance(&glyph.stroke.centre) - glyph.stroke.radius).abs()
+                    / glyph.stroke.radius;
+
+                (error < LINE_TOLERANCE
+                    && far.distance(&glyph.stroke.centre) > near.distance(&glyph.stroke.centre))
+                .then_some(error)
+            };
+
+            let consonant_error = consonants
+                .iter()
+                .filter_map(line_error)
+                .fold(f64::INFINITY, f64::min);
+            let vowel_error = vowels
+                .iter()
+                .filter_map(line_error)
+                .fold(f64::INFINITY, f64::min);
+            let glyphs = match consonant_error <= vowel_error {
+                true => &mut consonants,
+                false => &mut vowels,
+            };
+
+            if let Some(glyph) = nearest(glyphs, line_error) {
+                glyph.lines.push((near, far));
+            }
+        });
+
+    let mut groups = consonants
+        .iter()
+        .map(|consonant| {
+            let (base, base_confidence) = consonant_base(&consonant.stroke, size, style);
+            let (modifier, modifier_confidence) =
+                consonant_modifier(&base, consonant.dots, consonant.lines.len());
+            let letter = GallifreyanLetter::from_glyph(&base, modifier.as_ref())
+                .expect("The modifier should be chosen from the letters of the base.");
+
+            (
+                consonant,
+                base,
+                repeat(
+                    letter,
+                    base_confidence * modifier_confidence,
+                    consonant.stroke.doubled,
+                ),
+            )
+        })
+        .collect::<Vec<(&Glyph, Base, Vec<RecognisedLetter>)>>();
+    let mut standalone_vowels = Vec::new();
+
+    vowels.iter().for_each(|vowel| {
+        let host = groups
+            .iter_mut()
+            .filter(|(consonant, _, letters)| {
+                letters.len() == 1 + usize::from(consonant.stroke.doubled)
+                    && angle_between(consonant.angle(), vowel.angle())
+                        < (0.5 * consonant.stroke.radius / size).max(CIRCLE_TOLERANCE)
+            })
+            .min_by(|(consonant1, ..), (consonant2, ..)| {
+                angle_between(consonant1.angle(), vowel.angle())
+                    .total_cmp(&angle_between(consonant2.angle(), vowel.angle()))
+            });
+
+        match host {
+            Some((consonant, base, letters)) => {
+                let line_point = Vector2::from_polar(size, consonant.angle());
+                let centre = match base {
+                    Base::Crescent | Base::Full => consonant.stroke.centre,
+                    _ => line_point,
+                };
+                letters.append(&mut vowel_letters(vowel, line_point, centre, style));
+            }
+            None => {
+                let line_point = Vector2::from_polar(size, vowel.angle());
+                standalone_vowels.push((
+                    vowel.angle(),
+                    vowel_letters(vowel, line_point, line_point, style),
+                ))
+            }
+        }
+    });
+
+    let mut letters = groups
+        .into_iter()
+        .map(|(consonant, _, letters)| (consonant.angle(), letters))
+        .chain(standalone_vowels)
+        .collect::<Vec<(f64, Vec<RecognisedLetter>)>>();
+    letters.sort_by(|(angle1, _), (angle2, _)| {
+        reading_angle(*angle1).total_cmp(&reading_angle(*angle2))
+    });
+
+    Some(RecognisedWord {
+        letters: letters
+            .into_iter()
+            .flat_map(|(_, letters)| letters)
+            .collect(),
+        centre,
+        size,
+    })
+}
+
+/// How closely a measurement matches its expected value, from 1 down towards 0.
+fn closeness(error: f64, tolerance: f64) -> f64 {
+    (-(error / tolerance).powi(2)).exp()
+}
+
+/// The smallest angle between the directions `angle1` and `angle2`.
+fn angle_between(angle1: f64, angle2: f64) -> f64 {
+    ((angle1 - angle2 + PI).rem_euclid(2.0 * PI) - PI).abs()
+}
+
+/// The angle counterclockwise from the bottom of the word circle, where reading starts.
+fn reading_angle(angle: f64) -> f64 {
+    (angle + FRAC_PI_2 + CIRCLE_TOLERANCE).rem_euclid(2.0 * PI)
+}
+
+/// The glyph with the smallest error, ignoring glyphs without one.
+fn nearest<F>(glyphs: &mut [Glyph], error: F) -> Option<&mut Glyph>
+where
+    F: Fn(&Glyph) -> Option<f64>,
+{
+    glyphs
+        .iter_mut()
+        .filter_map(|glyph| error(glyph).map(|error| (error, glyph)))
+        .min_by(|(error1, _), (error2, _)| error1.total_cmp(error2))
+        .map(|(_, glyph)| glyph)
+}
+
+fn repeat(letter: GallifreyanLetter, confidence: f64, doubled: bool) -> Vec<RecognisedLetter> {
+    vec![RecognisedLetter { letter, confidence }; 1 + usize::from(doubled)]
+}
+
+/// Folds the inner stroke of each doubled letter into its outer stroke.
+fn merge_doubled(mut strokes: Vec<Stroke>, style: &Style) -> Vec<Stroke> {
+    strokes.sort_by(|stroke1, stroke2| stroke2.radius.total_cmp(&stroke1.radius));
+
+    strokes
+        .into_iter()
+        .fold(Vec::new(), |mut merged: Vec<Stroke>, stroke| {
+            match merged.iter_mut().find(|outer| {
+                outer.centre.distance(&stroke.centre) < DOUBLED_TOLERANCE * outer.radius
+                    && (stroke.radius / outer.radius - style.double_base_ratio).abs()
+                        < DOUBLED_TOLERANCE
+            }) {
+                Some(outer) => outer.doubled = true,
+                None => merged.push(stroke),
+            }
+
+            merged
+        })
+}
+
+/// Estimates the letter size of the drawing from its strokes. When all strokes are about the same
+/// size, the size of a word of that many letters decides whether they are consonants or vowels.
+fn letter_size(strokes: &[Stroke], size: f64, style: &Style) -> f64 {
+    let largest = strokes
+        .iter()
+        .map(|stroke| stroke.radius)
+        .fold(0.0, f64::max);
+    let smallest = strokes
+        .iter()
+        .map(|stroke| stroke.radius)
+        .fold(f64::INFINITY, f64::min);
+
+    if strokes.is_empty() {
+        return size / GallifreyanWord::size_ratio(0);
+    }
+    if smallest < style.vowel_ratio.sqrt() * largest || strokes.iter().any(|stroke| stroke.is_arc) {
+        return largest;
+    }
+
+    let expected = size / GallifreyanWord::size_ratio(strokes.len());
+    match (largest / expected).ln().abs() < (largest / (style.vowel_ratio * expected)).ln().abs() {
+        true => largest,
+        false => largest / style.vowel_ratio,
+    }
+}
+
+/// The consonant base whose stroke is closest to `stroke`, by how deep its centre lies inside the
+/// word circle and whether it is cut by the word circle.
+fn consonant_base(stroke: &Stroke, size: f64, style: &Style) -> (Base, f64) {
+    let depth = (size - stroke.centre.rho()) / stroke.radius;
+
+    [
+        (Base::Crescent, true, style.crescent_base_ratio),
+        (Base::Quarter, true, 0.0),
+        (Base::Full, false, style.full_base_ratio),
+        (Base::New, false, 0.0),
+    ]
+    .into_iter()
+    .map(|(base, is_arc, depth_ratio)| {
+        let cut = match is_arc == stroke.is_arc {
+            true => 1.0,
+            false => MODIFIER_PENALTY,
+        };
+
+        (
+            base,
+            closeness(depth - depth_ratio, PLACEMENT_TOLERANCE) * cut,
+        )
+    })
+    .max_by(|(_, confidence1), (_, confidence2)| confidence1.total_cmp(confidence2))
+    .expect("There should be a consonant base.")
+}
+
+/// The modifier of `base` closest to `dots` dots and `lines` lines.
+fn consonant_modifier(base: &Base, dots: usize, lines: usize) -> (Option<Modifier>, f64) {
+    [
+        (None, 0, 0),
+        (Some(Modifier::Dot1), 1, 0),
+        (Some(Modifier::Dot2), 2, 0),
+        (Some(Modifier::Dot3), 3, 0),
+        (Some(Modifier::Dot4), 4, 0),
+        (Some(Modifier::Line1), 0, 1),
+        (Some(Modifier::Line2), 0, 2),
+        (Some(Modifier::Line3), 0, 3),
+    ]
+    .into_iter()
+    .filter(|(modifier, ..)| GallifreyanLetter::from_glyph(base, modifier.as_ref()).is_some())
+    .map(|(modifier, modifier_dots, modifier_lines)| {
+        let difference = dots.abs_diff(modifier_dots) + lines.abs_diff(modifier_lines);
+
+        (modifier, MODIFIER_PENALTY.powi(difference as i32))
+    })
+    .max_by(|(_, confidence1), (_, confidence2)| confidence1.total_cmp(confidence2))
+    .expect("Every base should have a letter.")
+}
+
+/// The letters of a vowel drawn around `origin`, by how far its centre lies from `origin` along
+/// the word's radius and which way its line points.
+fn vowel_letters(
+    vowel: &Glyph,
+    line_point: Vector2,
+    host_centre: Vector2,
+    style: &Style,
+) -> Vec<RecognisedLetter> {
+    let letter_size = vowel.stroke.radius / style.vowel_ratio;
+    let outward = Vector2::from_polar(1.0, line_point.phi());
+    let offset = |base: &Base| {
+        let origin = match base.attaches_to_word_line() {
+            true => line_point,
+            false => host_centre,
+        };
+        (vowel.stroke.centre - origin).dot(&outward) / letter_size
+    };
+
+    let (base, placement) = [
+        (Base::Moon(0.0), style.moon_base_ratio),
+        (Base::Moon(PI), -style.moon_base_ratio),
+        (Base::Core, 0.0),
+    ]
+    .into_iter()
+    .map(|(base, expected)| {
+        let confidence = closeness(offset(&base) - expected, PLACEMENT_TOLERANCE);
+        (base, confidence)
+    })
+    .max_by(|(_, confidence1), (_, confidence2)| confidence1.total_cmp(confidence2))
+    .expect("There should be a vowel base.");
+
+    let expected_lines = usize::from(matches!(base, Base::Core) && !vowel.lines.is_empty());
+    let modifier =
+        vowel
+            .lines
+            .first()
+            .filter(|_| expected_lines == 1)
+            .map(|(near, far)| match (*far - *near).dot(&outward) < 0.0 {
+                true => Modifier::VowelLine1(0.0),
+                false => Modifier::VowelLine1(PI),
+            });
+    let letter = GallifreyanLetter::from_glyph(&base, modifier.as_ref())
+        .expect("Every vowel base should have a letter.");
+    let confidence =
+        placement * MODIFIER_PENALTY.powi(vowel.lines.len().abs_diff(expected_lines) as i32);
+
+    repeat(letter, confidence, vowel.stroke.doubled)
+}
@@ -0,0 +1,398 @@
+use crate::connections::connected_modifier_shapes;
+use crate::glyphs::*;
+use crate::layout::{Element, Layout, Role};
+use crate::letters::*;
+use crate::shapes::{Shape, Tessellation};
+use geomath::prelude::coordinates::Polar;
+use geomath::vector::Vector2;
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::iter;
+use std::str::FromStr;
+
+/// A sentence of Gallifreyan words laid out around an enclosing sentence circle.
+///
+/// Each word keeps the size derived from its letter count and is placed counterclockwise around
+/// the sentence circle, starting at the bottom, with an angular share proportional to its size.
+/// Punctuation marks are drawn on the sentence circle in the gaps between words.
+pub struct GallifreyanSentence {
+    words: Vec<GallifreyanWord>,
+    punctuation: Vec<(usize, Punctuation)>,
+    positions: Vec<f64>,
+    origins: Vec<Vector2>,
+    /// The modifier shapes of every character of every word, relative to the origin of its word,
+    /// with the lines of line modifiers connected once for the whole layout.
+    modifier_shapes: Vec<Vec<Vec<Shape>>>,
+    size: f64,
+    style: Style,
+}
+
+impl FromStr for GallifreyanSentence {
+    type Err = ParseGallifreyanLetterError;
+
+    fn from_str(sentence: &str) -> Result<Self, Self::Err> {
+        let mut words = Vec::new();
+        let mut punctuation = Vec::new();
+        let mut word_start = None;
+
+        for (byte_offset, character) in sentence
+            .char_indices()
+            .chain(iter::once((sentence.len(), ' ')))
+        {
+            let mark = character.to_string().parse::<Punctuation>().ok();
+
+            if character.is_whitespace() || mark.is_some() {
+                if let Some(start) = word_start.take() {
+                    words.push(Self::parse_word(sentence, start, byte_offset)?);
+                }
+                if let Some(mark) = mark {
+                    punctuation.push((words.len(), mark));
+                }
+            } else if word_start.is_none() {
+                word_start = Some(byte_offset);
+            }
+        }
+
+        Ok(GallifreyanSentence {
+            punctuation,
+            ..GallifreyanSentence::new(words)
+        })
+    }
+}
+
+impl TryFrom<&str> for GallifreyanSentence {
+    type Error = ParseGallifreyanLetterError;
+
+    fn try_from(sentence: &str) -> Result<Self, Self::Error> {
+        sentence.parse()
+    }
+}
+
+impl GallifreyanSentence {
+    const WORD_SPACING: f64 = 1.0;
+    const SENTENCE_MARGIN: f64 = 1.0;
+
+    fn parse_word(
+        sentence: &str,
+        start: usize,
+        end: usize,
+    ) -> Result<GallifreyanWord, ParseGallifreyanLetterError> {
+        let char_start = sentence[..start].chars().count();
+
+        sentence[start..end]
+            .parse::<GallifreyanWord>()
+            .map(|word| word.shifted(start, char_start))
+            .map_err(|error| {
+                ParseGallifreyanLetterError::new(
+                    sentence,
+                    start + error.byte_offset(),
+                    char_start + error.char_offset(),
+                )
+            })
+    }
+
+    pub fn new(words: Vec<GallifreyanWord>) -> GallifreyanSentence {
+        let outer_sizes = words
+            .iter()
+            .map(|word| word.outer_size())
+            .collect::<Vec<f64>>();
+        let total_size = outer_sizes.iter().sum::<f64>();
+
+        let radius = match outer_sizes.len() {
+            0..=1 => 0.0,
+            count => (0..count)
+                .map(|index| {
+                    let pair_size = outer_sizes[index] + outer_sizes[(index + 1) % count];
+
+                    (pair_size + Self::WORD_SPACING)
+                        / (2.0 * (FRAC_PI_2 * pair_size / total_size).sin())
+                })
+                .fold(0.0, f64::max),
+        };
+
+        let mut position = -FRAC_PI_2;
+        let positions = outer_sizes
+            .iter()
+            .enumerate()
+            .map(|(index, outer_size)| {
+                if index > 0 {
+                    position += PI * (outer_sizes[index - 1] + outer_size) / total_size;
+                }
+
+                position
+            })
+            .collect::<Vec<f64>>();
+        let origins = positions
+            .iter()
+            .map(|position| Vector2::from_polar(radius, *position))
+            .collect::<Vec<Vector2>>();
+
+        let size = outer_sizes
+            .iter()
+            .map(|outer_size| radius + outer_size)
+            .fold(0.0, f64::max)
+            + Self::SENTENCE_MARGIN;
+
+        let modifier_shapes = connected_modifier_shapes(
+            &origins
+                .iter()
+                .copied()
+                .zip(words.iter())
+                .collect::<Vec<(Vector2, &GallifreyanWord)>>(),
+        );
+
+        GallifreyanSentence {
+            words,
+            punctuation: Vec::new(),
+            positions,
+            origins,
+            modifier_shapes,
+            size,
+            style: Style::default(),
+        }
+    }
+
+    /// Draws every word and punctuation mark with the proportions of `style`, laying the words
+    /// out again around the resized sentence circle.
+    pub fn with_style(self, style: Style) -> GallifreyanSentence {
+        let words = self
+            .words
+            .into_iter()
+            .map(|word| word.with_style(style))
+            .collect();
+
+        GallifreyanSentence {
+            punctuation: self.punctuation,
+            style,
+            ..GallifreyanSentence::new(words)
+        }
+    }
+
+    /// Stacks consecutive consonants of the same base in every word, like
+    /// [`GallifreyanWord::with_stacking`], laying the words out again.
+    pub fn with_stacking(self, stacking: bool) -> GallifreyanSentence {
+        let words = self
+            .words
+            .into_iter()
+            .map(|word| word.with_stacking(stacking))
+            .collect();
+
+        GallifreyanSentence {
+            punctuation: self.punctuation,
+            style: self.style,
+            ..GallifreyanSentence::new(words)
+        }
+    }
+
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// The radius of the sentence circle.
+    pub fn size(&self) -> f64 {
+        self.size
+    }
+
+    pub fn words(&self) -> &[GallifreyanWord] {
+        &self.words
+    }
+
+    /// The centres of the word circles, in the same order as `words`.
+    pub fn word_origins(&self) -> &[Vector2] {
+        &self.origins
+    }
+
+    /// The punctuation marks of the sentence, each with the number of words preceding it.
+    pub fn punctuation(&self) -> &[(usize, Punctuation)] {
+        &self.punctuation
+    }
+
+    /// The shapes of the sentence circle, of every word moved to its origin and of the
+    /// punctuation marks. The lines of line modifiers are connected within and across words.
+    pub fn to_shapes(&self) -> Vec<Shape> {
+        self.to_layout().to_shapes()
+    }
+
+    /// Lays the sentence out like [`GallifreyanSentence::to_shapes`], keeping the word, letter and
+    /// part drawn by every shape.
+    pub fn to_layout(&self) -> Layout {
+        let sentence_circle = Element::new(self.sentence_circle(), Role::SentenceCircle);
+        let word_elements = self
+            .words
+            .iter()
+            .zip(self.origins.iter())
+            .enumerate()
+            .zip(&self.modifier_shapes)
+            .flat_map(|((index, (word, origin)), modifier_shapes)| {
+                word.layout_with_modifiers(modifier_shapes.clone())
+                    .translate(*origin)
+                    .elements()
+                    .iter()
+                    .map(|element| element.with_word(index))
+                    .collect::<Vec<Element>>()
+            });
+        let punctuation_elements =
+            self.punctuation_origins()
+                .into_iter()
+                .flat_map(|(origin, mark)| {
+                    mark.to_shapes(origin, self.style.letter_size, &self.style)
+                        .into_iter()
+                        .map(move |shape| Element::new(shape, Role::Punctuation(mark)))
+                });
+
+        Layout::new(
+            iter::once(sentence_circle)
+                .chain(word_elements)
+                .chain(punctuation_elements)
+                .collect(),
+        )
+    }
+
+    pub fn draw_sentence_circle(&self) -> Vec<(f32, f32)> {
+        self.draw_sentence_circle_with(Tessellation::default())
+    }
+
+    /// Draws the sentence circle like [`draw_sentence_circle`](Self::draw_sentence_circle),
+    /// tessellated with `tessellation`.
+    pub fn draw_sentence_circle_with(&self, tessellation: Tessellation) -> Vec<(f32, f32)> {
+        self.sentence_circle().tessellate(tessellation)
+    }
+
+    pub fn draw_bases(&self) -> Vec<Vec<(f32, f32)>> {
+        self.draw_bases_with(Tessellation::default())
+    }
+
+    /// Draws the bases like [`draw_bases`](Self::draw_bases), tessellated with `tessellation`.
+    pub fn draw_bases_with(&self, tessellation: Tessellation) -> Vec<Vec<(f32, f32)>> {
+        self.placed_characters()
+            .flat_map(|(origin, character)| {
+                character
+                    .base_shapes()
+                    .into_iter()
+                    .map(move |shape| shape.translate(origin).tessellate(tessellation))
+            })
+            .collect()
+    }
+
+    /// Draws the line modifiers of every word along with the punctuation marks made of lines and
+    /// circles.
+    pub fn draw_lines(&self) -> Vec<Vec<(f32, f32)>> {
+        self.draw_lines_with(Tessellation::default())
+    }
+
+    /// Draws the lines like [`draw_lines`](Self::draw_lines), tessellated with `tessellation`.
+    pub fn draw_lines_with(&self, tessellation: Tessellation) -> Vec<Vec<(f32, f32)>> {
+        self.modifier_and_punctuation_shapes()
+            .iter()
+            .filter(|shape| !shape.is_dot())
+            .map(|shape| shape.tessellate(tessellation))
+            .collect()
+    }
+
+    /// Draws the dot modifiers of every word along with the punctuation marks made of dots.
+    pub fn draw_dots(&self) -> Vec<Vec<(f32, f32)>> {
+        self.draw_dots_with(Tessellation::default())
+    }
+
+    /// Draws the dots like [`draw_dots`](Self::draw_dots), tessellated with `tessellation`.
+    pub fn draw_dots_with(&self, tessellation: Tessellation) -> Vec<Vec<(f32, f32)>> {
+        self.modifier_and_punctuation_shapes()
+            .iter()
+            .filter(|shape| shape.is_dot())
+            .map(|shape| shape.tessellate(tessellation))
+            .collect()
+    }
+
+    pub fn draw_edges(&self) -> Vec<Vec<(f32, f32)>> {
+        self.draw_edges_with(Tessellation::default())
+    }
+
+    /// Draws the edges like [`draw_edges`](Self::draw_edges), tessellated with `tessellation`.
+    pub fn draw_edges_with(&self, tessellation: Tessellation) -> Vec<Vec<(f32, f32)>> {
+        self.words
+            .iter()
+            .zip(self.origins.iter())
+            .flat_map(|(word, origin)| {
+                word.edge_shapes()
+                    .into_iter()
+                    .map(|shape| shape.translate(*origin).tessellate(tessellation))
+            })
+            .collect()
+    }
+
+    fn sentence_circle(&self) -> Shape {
+        Shape::Circle {
+            centre: Vector2::from_polar(0.0, 0.0),
+            radius: self.size,
+        }
+    }
+
+    fn modifier_and_punctuation_shapes(&self) -> Vec<Shape> {
+        self.origins
+            .iter()
+            .zip(&self.modifier_shapes)
+            .flat_map(|(origin, modifier_shapes)| {
+                modifier_shapes
+                    .iter()
+                    .flatten()
+                    .map(move |shape| shape.translate(*origin))
+            })
+            .chain(self.punctuation_shapes())
+            .collect()
+    }
+
+    fn punctuation_shapes(&self) -> Vec<Shape> {
+        self.punctuation_origins()
+            .into_iter()
+            .flat_map(|(origin, mark)| mark.to_shapes(origin, self.style.letter_size, &self.style))
+            .collect()
+    }
+
+    /// The point on the sentence circle of every punctuation mark. Marks sharing a gap are spread
+    /// along the circle in reading order.
+    fn punctuation_origins(&self) -> Vec<(Vector2, Punctuation)> {
+        let step_size = 1.5 * self.style.letter_size / self.size;
+
+        self.punctuation
+            .iter()
+            .enumerate()
+            .map(|(index, (gap, mark))| {
+                let count = self
+                    .punctuation
+                    .iter()
+                    .filter(|(other, _)| other == gap)
+                    .count();
+                let order = self.punctuation[..index]
+                    .iter()
+                    .filter(|(other, _)| other == gap)
+                    .count();
+                let position =
+                    self.gap_position(*gap) + (order as f64 - (count - 1) as f64 / 2.0) * step_size;
+
+                (Vector2::from_polar(self.size, position), *mark)
+            })
+            .collect()
+    }
+
+    /// The angle on the sentence circle halfway between the words around the gap preceded by
+    /// `gap` words.
+    fn gap_position(&self, gap: usize) -> f64 {
+        match (self.positions.first(), self.positions.last()) {
+            (Some(first), Some(last)) if gap == 0 || gap == self.positions.len() => {
+                (first + 2.0 * PI + last) / 2.0
+            }
+            (Some(_), Some(_)) => (self.positions[gap - 1] + self.positions[gap]) / 2.0,
+            _ => -FRAC_PI_2,
+        }
+    }
+
+    fn placed_characters(&self) -> impl Iterator<Item = (Vector2, GallifreyanCharacter)> + '_ {
+        self.words
+            .iter()
+            .zip(self.origins.iter())
+            .flat_map(|(word, origin)| {
+                word.to_gallifreyan_characters()
+                    .into_iter()
+                    .map(move |character| (*origin, character))
+            })
+    }
+}
@@ -0,0 +1,374 @@
+use geomath::prelude::coordinates::Polar;
+use geomath::prelude::Metric;
+use geomath::vector::Vector2;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+const MAX_SEGMENTS: f64 = 65536.0;
+
+/// How finely arcs and circles are split into the segments of a polyline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tessellation {
+    /// The maximum distance between a segment and the arc it replaces.
+    ChordTolerance(f64),
+    /// The maximum length of a segment.
+    MaxSegmentLength(f64),
+}
+
+impl Default for Tessellation {
+    fn default() -> Self {
+        Tessellation::ChordTolerance(0.01)
+    }
+}
+
+impl Tessellation {
+    /// The number of segments needed to draw an arc of `radius` spanning `span` radians. A
+    /// tolerance or length that is not positive, or NaN, asks for the finest tessellation.
+    pub fn segments(&self, radius: f64, span: f64) -> usize {
+        let step = match *self {
+            Tessellation::ChordTolerance(tolerance) => {
+                2.0 * (1.0 - (tolerance.max(f64::MIN_POSITIVE) / radius).min(1.0)).acos()
+            }
+            Tessellation::MaxSegmentLength(length) => {
+                2.0 * (length.max(f64::MIN_POSITIVE) / (2.0 * radius))
+                    .min(1.0)
+                    .asin()
+            }
+        };
+
+        (span.abs() / step).ceil().clamp(1.0, MAX_SEGMENTS) as usize
+    }
+}
+
+/// An analytic shape of a Gallifreyan drawing in cartesian coordinates.
+///
+/// With the `serde` feature, shapes are tagged by a `type` field and points are `{ "x", "y" }`
+/// objects.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type"))]
+pub enum Shape {
+    /// An arc of the circle around `centre`, going counterclockwise from the `start` angle to the
+    /// `end` angle. The angles are in radians and `end` is always greater than `start`.
+    Arc {
+        #[cfg_attr(feature = "serde", serde(with = "point"))]
+        centre: Vector2,
+        radius: f64,
+        start: f64,
+        end: f64,
+    },
+    Circle {
+        #[cfg_attr(feature = "serde", serde(with = "point"))]
+        centre: Vector2,
+        radius: f64,
+    },
+    Dot {
+        #[cfg_attr(feature = "serde", serde(with = "point"))]
+        centre: Vector2,
+    },
+    Segment {
+        #[cfg_attr(feature = "serde", serde(with = "point"))]
+        start: Vector2,
+        #[cfg_attr(feature = "serde", serde(with = "point"))]
+        end: Vector2,
+    },
+}
+
+/// An axis-aligned box, from its corner with the smallest coordinates to the opposite one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BoundingBox {
+    #[cfg_attr(feature = "serde", serde(with = "point"))]
+    pub min: Vector2,
+    #[cfg_attr(feature = "serde", serde(with = "point"))]
+    pub max: Vector2,
+}
+
+impl BoundingBox {
+    /// The box holding a single point.
+    pub fn point(point: Vector2) -> BoundingBox {
+        BoundingBox {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// The smallest box holding both boxes.
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox {
+            min: Vector2::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Vector2::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    /// The box grown by `margin` on every side.
+    pub fn expand(&self, margin: f64) -> BoundingBox {
+        BoundingBox {
+            min: Vector2::new(self.min.x - margin, self.min.y - margin),
+            max: Vector2::new(self.max.x + margin, self.max.y + margin),
+        }
+    }
+
+    /// Whether the boxes share at least one point.
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+
+    pub fn contains(&self, point: Vector2) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+}
+
+/// Serializes a `Vector2`, which has no serde support of its own, as a `{ "x", "y" }` object.
+#[cfg(feature = "serde")]
+pub(crate) mod point {
+    use geomath::vector::Vector2;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    pub fn serialize<S: Serializer>(vector: &Vector2, serializer: S) -> Result<S::Ok, S::Error> {
+        Point {
+            x: vector.x,
+            y: vector.y,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vector2, D::Error> {
+        Point::deserialize(deserializer).map(|point| Vector2::new(point.x, point.y))
+    }
+}
+
+impl Shape {
+    /// Creates the arc going counterclockwise from `start` to `end`, wrapping past a full turn when
+    /// `end` is smaller than `start`. An arc spanning a full turn is a circle.
+    pub fn arc(centre: Vector2, radius: f64, start: f64, end: f64) -> Shape {
+        match (end - start).rem_euclid(2.0 * PI) {
+            span if !(1e-9..=2.0 * PI - 1e-9).contains(&span) => Shape::Circle { centre, radius },
+            span => Shape::Arc {
+                centre,
+                radius,
+                start,
+                end: start + span,
+            },
+        }
+    }
+
+    pub fn is_dot(&self) -> bool {
+        matches!(self, Shape::Dot { .. })
+    }
+
+    /// The smallest box holding the shape. A dot is a single point.
+    pub fn bounds(&self) -> BoundingBox {
+        match *self {
+            Shape::Arc {
+                centre,
+                radius,
+                start,
+                end,
+            } => {
+                let point = |angle: f64| centre + Vector2::from_polar(radius, angle);
+                // The arc reaches out to the circle's box wherever it crosses one of the axes.
+                (0..4)
+                    .map(|quarter| {
+                        let angle = quarter as f64 * PI / 2.0;
+                        angle + ((start - angle) / (2.0 * PI)).ceil() * 2.0 * PI
+                    })
+                    .filter(|&angle| angle <= end)
+                    .fold(
+                        BoundingBox::point(point(start)).union(&BoundingBox::point(point(end))),
+                        |bounds, angle| bounds.union(&BoundingBox::point(point(angle))),
+                    )
+            }
+            Shape::Circle { centre, radius } => BoundingBox::point(centre).expand(radius),
+            Shape::Dot { centre } => BoundingBox::point(centre),
+            Shape::Segment { start, end } => {
+                BoundingBox::point(start).union(&BoundingBox::point(end))
+            }
+        }
+    }
+
+    /// The distance from `point` to the nearest point drawn by the shape.
+    pub fn distance(&self, point: Vector2) -> f64 {
+        match *self {
+            Shape::Arc {
+                centre,
+                radius,
+                start,
+                end,
+            } => {
+                let offset = point - centre;
+                let angle = start + (offset.phi() - start).rem_euclid(2.0 * PI);
+
+                match angle <= end {
+                    true => (offset.rho() - radius).abs(),
+                    false => [start, end]
+                        .map(|angle| (centre + Vector2::from_polar(radius, angle)).distance(&point))
+                        .into_iter()
+                        .fold(f64::INFINITY, f64::min),
+                }
+            }
+            Shape::Circle { centre, radius } => ((point - centre).rho() - radius).abs(),
+            Shape::Dot { centre } => centre.distance(&point),
+            Shape::Segment { start, end } => {
+                let length = start.distance2(&end);
+                let along = match length > 0.0 {
+                    true => ((point - start).dot(&(end - start)) / length).clamp(0.0, 1.0),
+                    false => 0.0,
+                };
+
+                (start + (end - start) * along).distance(&point)
+            }
+        }
+    }
+
+    pub fn translate(&self, offset: Vector2) -> Shape {
+        match *self {
+            Shape::Arc {
+                centre,
+                radius,
+                start,
+                end,
+            } => Shape::Arc {
+                centre: centre + offset,
+                radius,
+                start,
+                end,
+            },
+            Shape::Circle { centre, radius } => Shape::Circle {
+                centre: centre + offset,
+                radius,
+            },
+            Shape::Dot { centre } => Shape::Dot {
+                centre: centre + offset,
+            },
+            Shape::Segment { start, end } => Shape::Segment {
+                start: start + offset,
+                end: end + offset,
+            },
+        }
+    }
+
+    /// Tessellates the shape into a polyline with the default tessellation.
+    pub fn to_polyline(&self) -> Vec<(f32, f32)> {
+        self.tessellate(Tessellation::default())
+    }
+
+    /// Tessellates the shape into a polyline. Arcs keep their exact end points and a dot is a
+    /// single point.
+    pub fn tessellate(&self, tessellation: Tessellation) -> Vec<(f32, f32)> {
+        match *self {
+            Shape::Arc {
+                centre,
+                radius,
+                start,
+                end,
+            } => tessellate_arc(centre, radius, (start, end), tessellation),
+            Shape::Circle { centre, radius } => {
+                tessellate_arc(centre, radius, (0.0, 2.0 * PI), tessellation)
+            }
+            Shape::Dot { centre } => vec![(centre.x as f32, centre.y as f32)],
+            Shape::Segment { start, end } => vec![
+                (start.x as f32, start.y as f32),
+                (end.x as f32, end.y as f32),
+            ],
+        }
+    }
+}
+
+/// The smallest distance between the shapes of `first` and those of `second`, measured from the
+/// points of each, tessellated with `tessellation`, to the shapes of the other.
+pub(crate) fn clearance(first: &[Shape], second: &[Shape], tessellation: Tessellation) -> f64 {
+    let one_way = |from: &[Shape], to: &[Shape]| {
+        from.iter()
+            .flat_map(|shape| shape.tessellate(tessellation))
+            .map(|(x, y)| Vector2::new(x as f64, y as f64))
+            .flat_map(|point| to.iter().map(move |shape| shape.distance(point)))
+            .fold(f64::INFINITY, f64::min)
+    };
+
+    one_way(first, second).min(one_way(second, first))
+}
+
+fn tessellate_arc(
+    centre: Vector2,
+    radius: f64,
+    range: (f64, f64),
+    tessellation: Tessellation,
+) -> Vec<(f32, f32)> {
+    let segments = tessellation.segments(radius, range.1 - range.0);
+
+    (0..=segments)
+        .map(|segment| match segment == segments {
+            true => range.1,
+            false => range.0 + (range.1 - range.0) * segment as f64 / segments as f64,
+        })
+        .map(|angle| centre + Vector2::from_polar(radius, angle))
+        .map(|vector| (vector.x as f32, vector.y as f32))
+        .collect::<Vec<(f32, f32)>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_tessellations_are_the_finest() {
+        let circle = Shape::Circle {
+            centre: Vector2::new(0.0, 0.0),
+            radius: 1.0,
+        };
+        let finest = MAX_SEGMENTS as usize + 1;
+
+        [
+            Tessellation::ChordTolerance(-0.1),
+            Tessellation::ChordTolerance(0.0),
+            Tessellation::ChordTolerance(f64::NAN),
+            Tessellation::MaxSegmentLength(-1.0),
+            Tessellation::MaxSegmentLength(f64::NAN),
+        ]
+        .into_iter()
+        .for_each(|tessellation| {
+            assert_eq!(
+                circle.tessellate(tessellation).len(),
+                finest,
+                "{tessellation:?}"
+            );
+        });
+    }
+
+    #[test]
+    fn segments_follow_the_tessellation() {
+        let circle = Shape::Circle {
+            centre: Vector2::new(0.0, 0.0),
+            radius: 1.0,
+        };
+
+        assert_eq!(
+            circle
+                .tessellate(Tessellation::ChordTolerance(f64::INFINITY))
+                .len(),
+            3
+        );
+        assert_eq!(
+            circle.tessellate(Tessellation::MaxSegmentLength(0.1)).len(),
+            64
+        );
+    }
+}
@@ -0,0 +1,735 @@
+use crate::colours::{Colour, Colouring};
+use crate::glyphs::Style;
+use crate::layout::Layout;
+use crate::letters::*;
+use crate::numbers::*;
+use crate::recognition::*;
+use crate::sentences::*;
+use crate::shapes::Shape;
+use core::fmt;
+use geomath::prelude::coordinates::Polar;
+use geomath::vector::Vector2;
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt::{Display, Write};
+
+const MARGIN: f64 = 1.0;
+
+/// The presentation attributes of an SVG document, in the units of the glyphs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgOptions {
+    /// The colour of the strokes and dots, as any SVG colour.
+    pub colour: String,
+    /// The colour filling the whole document, if any.
+    pub background: Option<String>,
+    pub stroke_width: f64,
+    pub dot_radius: f64,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            colour: String::from("black"),
+            background: None,
+            stroke_width: 0.1,
+            dot_radius: 0.2,
+        }
+    }
+}
+
+/// Writes a standalone SVG document of the word, centred on its word circle.
+pub fn word_to_svg(word: &GallifreyanWord) -> String {
+    shapes_to_svg(&word.to_shapes(), word.outer_size() + MARGIN)
+}
+
+/// Writes a standalone SVG document of the sentence, centred on its sentence circle.
+pub fn sentence_to_svg(sentence: &GallifreyanSentence) -> String {
+    shapes_to_svg(&sentence.to_shapes(), sentence.size() + MARGIN)
+}
+
+/// Writes a standalone SVG document of the number, centred on its number circle.
+pub fn number_to_svg(number: &GallifreyanNumber) -> String {
+    shapes_to_svg(&number.to_shapes(), number.size() + 3.0 * MARGIN)
+}
+
+/// Writes a standalone SVG document of the shapes, spanning `size` around the origin. The y axis
+/// is flipped so the glyphs read the same as the cartesian plots.
+pub fn shapes_to_svg(shapes: &[Shape], size: f64) -> String {
+    shapes_to_svg_with(shapes, size, &SvgOptions::default())
+}
+
+/// Writes a standalone SVG document of the shapes like `shapes_to_svg`, presented with `options`.
+pub fn shapes_to_svg_with(shapes: &[Shape], size: f64, options: &SvgOptions) -> String {
+    let shapes = shapes
+        .iter()
+        .map(|shape| (*shape, options.colour.as_str()))
+        .collect::<Vec<(Shape, &str)>>();
+
+    svg_document(&shapes, size, options)
+}
+
+/// Writes a standalone SVG document of the layout like `shapes_to_svg_with`, drawing every element
+/// in the colour `colouring` picks for it instead of `options.colour`.
+pub fn layout_to_svg_with(
+    layout: &Layout,
+    size: f64,
+    options: &SvgOptions,
+    colouring: &impl Colouring,
+) -> String {
+    let shapes = layout
+        .elements()
+        .iter()
+        .map(|element| (element.shape, colouring.colour(element)))
+        .collect::<Vec<(Shape, Colour)>>();
+
+    coloured_shapes_to_svg(&shapes, size, options)
+}
+
+/// Writes a standalone SVG document of the shapes like `shapes_to_svg_with`, drawing each shape in
+/// its own colour instead of `options.colour`.
+pub fn coloured_shapes_to_svg(
+    shapes: &[(Shape, Colour)],
+    size: f64,
+    options: &SvgOptions,
+) -> String {
+    let colours = shapes
+        .iter()
+        .map(|(_, colour)| colour.to_hex())
+        .collect::<Vec<String>>();
+    let shapes = shapes
+        .iter()
+        .zip(colours.iter())
+        .map(|((shape, _), colour)| (*shape, colour.as_str()))
+        .collect::<Vec<(Shape, &str)>>();
+
+    svg_document(&shapes, size, options)
+}
+
+/// Writes the shapes grouped by colour, keeping the order in which the colours first appear.
+fn svg_document(shapes: &[(Shape, &str)], size: f64, options: &SvgOptions) -> String {
+    let mut colours: Vec<(&str, String, String)> = Vec::new();
+
+    shapes.iter().for_each(|&(shape, colour)| {
+        let index = colours
+            .iter()
+            .position(|(other, _, _)| *other == colour)
+            .unwrap_or_else(|| {
+                colours.push((colour, String::new(), String::new()));
+                colours.len() - 1
+            });
+        let (_, strokes, dots) = &mut colours[index];
+
+        let _ = match shape {
+            Shape::Arc {
+                centre,
+                radius,
+                start,
+                end,
+            } => writeln!(
+                strokes,
+                r#"      <path d="M {:.3} {:.3} A {:.3} {:.3} 0 {} 1 {:.3} {:.3}"/>"#,
+                centre.x + radius * start.cos(),
+                centre.y + radius * start.sin(),
+                radius,
+                radius,
+                u8::from(end - start > PI),
+                centre.x + radius * end.cos(),
+                centre.y + radius * end.sin(),
+            ),
+            Shape::Circle { centre, radius } => writeln!(
+                strokes,
+                r#"      <circle cx="{:.3}" cy="{:.3}" r="{:.3}"/>"#,
+                centre.x, centre.y, radius
+            ),
+            Shape::Dot { centre } => writeln!(
+                dots,
+                r#"      <circle cx="{:.3}" cy="{:.3}" r="{:.3}"/>"#,
+                centre.x, centre.y, options.dot_radius
+            ),
+            Shape::Segment { start, end } => writeln!(
+                strokes,
+                r#"      <line x1="{:.3}" y1="{:.3}" x2="{:.3}" y2="{:.3}"/>"#,
+                start.x, start.y, end.x, end.y
+            ),
+        };
+    });
+
+    let background = options.background.as_ref().map_or(String::new(), |colour| {
+        format!(
+            "  <rect x=\"{:.3}\" y=\"{:.3}\" width=\"{:.3}\" height=\"{:.3}\" fill=\"{}\"/>\n",
+            -size,
+            -size,
+            2.0 * size,
+            2.0 * size,
+            colour
+        )
+    });
+    let groups = colours
+        .iter()
+        .map(|(colour, strokes, dots)| {
+            format!(
+                concat!(
+                    r#"    <g fill="none" stroke="{}" stroke-width="{}" stroke-linecap="round">"#,
+                    "\n{}    </g>\n",
+                    r#"    <g fill="{}">"#,
+                    "\n{}    </g>\n"
+                ),
+                colour, options.stroke_width, strokes, colour, dots
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{:.3} {:.3} {:.3} {:.3}">"#,
+            "\n{}",
+            r#"  <g transform="scale(1 -1)">"#,
+            "\n{}  </g>\n</svg>\n"
+        ),
+        -size,
+        -size,
+        2.0 * size,
+        2.0 * size,
+        background,
+        groups
+    )
+}
+
+/// The error returned when an SVG document cannot be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseSvgError {
+    reason: String,
+    byte_offset: usize,
+}
+
+impl ParseSvgError {
+    fn new(reason: impl Into<String>, byte_offset: usize) -> ParseSvgError {
+        ParseSvgError {
+            reason: reason.into(),
+            byte_offset,
+        }
+    }
+
+    /// What could not be read.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    /// The byte offset in the document of the element that could not be read.
+    pub fn byte_offset(&self) -> usize {
+        self.byte_offset
+    }
+}
+
+impl Display for ParseSvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.reason, self.byte_offset)
+    }
+}
+
+impl Error for ParseSvgError {}
+
+/// Reads the word drawn in an SVG document, expecting the proportions of `style`.
+pub fn svg_to_word(svg: &str, style: &Style) -> Result<RecognisedWord, ParseSvgError> {
+    recognise_word(&svg_to_shapes(svg)?, style)
+        .ok_or_else(|| ParseSvgError::new("no word circle", 0))
+}
+
+/// Reads the circles, arcs, lines and dots of an SVG document, with the y axis pointing up.
+///
+/// Filled circles are read as dots. Paths are read as lines and circular arcs, with curves
+/// replaced by the line to their end point. Shapes inside `defs` and similar containers are
+/// skipped.
+pub fn svg_to_shapes(svg: &str) -> Result<Vec<Shape>, ParseSvgError> {
+    let mut shapes = Vec::new();
+    let mut scopes = vec![Scope {
+        transform: Transform([1.0, 0.0, 0.0, -1.0, 0.0, 0.0]),
+        filled: true,
+        hidden: false,
+    }];
+    let mut position = 0;
+
+    while let Some(start) = svg[position..].find('<').map(|start| position + start) {
+        let rest = &svg[start..];
+        let terminator = match rest {
+            _ if rest.starts_with("<!--") => "-->",
+            _ if rest.starts_with("<![CDATA[") => "]]>",
+            _ if rest.starts_with("<?") => "?>",
+            _ if rest.starts_with("<!") || rest.starts_with("</") => ">",
+            _ => "",
+        };
+        if !terminator.is_empty() {
+            let end = rest
+                .find(terminator)
+                .ok_or_else(|| ParseSvgError::new("unterminated markup", start))?;
+            if rest.starts_with("</") && scopes.len() > 1 {
+                scopes.pop();
+            }
+            position = start + end + terminator.len();
+            continue;
+        }
+
+        let (element, length) = Element::parse(rest, start)?;
+        position = start + length;
+
+        let parent = scopes.last().expect("There should be a root scope.");
+        let scope = Scope {
+            transform: match element.attribute("transform") {
+                Some(transform) => parent.transform.then(&Transform::parse(transform, start)?),
+                None => parent.transform,
+            },
+            filled: element.attribute("fill").map_or(parent.filled, |fill| {
+                fill != "none" && fill != "transparent"
+            }),
+            hidden: parent.hidden
+                || matches!(
+                    element.name,
+                    "defs" | "clipPath" | "mask" | "marker" | "pattern" | "symbol"
+                ),
+        };
+
+        if !scope.hidden {
+            shapes.append(&mut element.to_shapes(&scope, start)?);
+        }
+        if !element.closed {
+            scopes.push(scope);
+        }
+    }
+
+    Ok(shapes)
+}
+
+/// The state inherited by the children of an SVG element.
+struct Scope {
+    transform: Transform,
+    filled: bool,
+    hidden: bool,
+}
+
+/// An affine transform `[a b c d e f]` mapping `(x, y)` to `(ax + cy + e, bx + dy + f)`.
+#[derive(Clone, Copy)]
+struct Transform([f64; 6]);
+
+impl Transform {
+    /// Parses an SVG transform list.
+    fn parse(transform: &str, byte_offset: usize) -> Result<Transform, ParseSvgError> {
+        let mut result = Transform([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+        for function in transform.split_inclusive(')') {
+            let Some((name, arguments)) = function.split_once('(') else {
+                continue;
+            };
+            let arguments = numbers(arguments.trim_end_matches(')'))
+                .ok_or_else(|| ParseSvgError::new("invalid transform", byte_offset))?;
+            let argument =
+                |index: usize, default: f64| arguments.get(index).copied().unwrap_or(default);
+
+            let next = match (name.trim().trim_start_matches(','), arguments.len()) {
+                ("matrix", 6) => Transform([
+                    arguments[0],
+                    arguments[1],
+                    arguments[2],
+                    arguments[3],
+                    arguments[4],
+                    arguments[5],
+                ]),
+                ("translate", 1..=2) => {
+                    Transform([1.0, 0.0, 0.0, 1.0, arguments[0], argument(1, 0.0)])
+                }
+                ("scale", 1..=2) => {
+                    Transform([arguments[0], 0.0, 0.0, argument(1, arguments[0]), 0.0, 0.0])
+                }
+                ("rotate", 1 | 3) => {
+                    let (sin, cos) = argument(0, 0.0).to_radians().sin_cos();
+                    let (x, y) = (argument(1, 0.0), argument(2, 0.0));
+                    Transform([1.0, 0.0, 0.0, 1.0, x, y])
+                        .then(&Transform([cos, sin, -sin, cos, 0.0, 0.0]))
+                        .then(&Transform([1.0, 0.0, 0.0, 1.0, -x, -y]))
+                }
+                _ => return Err(ParseSvgError::new("unsupported transform", byte_offset)),
+            };
+
+            result = result.then(&next);
+        }
+
+        Ok(result)
+    }
+
+    /// The transform applying `next` first and then `self`.
+    fn then(&self, next: &Transform) -> Transform {
+        let [a, b, c, d, e, f] = self.0;
+        let [a2, b2, c2, d2, e2, f2] = next.0;
+
+        Transform([
+            a * a2 + c * b2,
+            b * a2 + d * b2,
+            a * c2 + c * d2,
+            b * c2 + d * d2,
+            a * e2 + c * f2 + e,
+            b * e2 + d * f2 + f,
+        ])
+    }
+
+    fn apply(&self, x: f64, y: f64) -> Vector2 {
+        let [a, b, c, d, e, f] = self.0;
+        Vector2::new(a * x + c * y + e, b * x + d * y + f)
+    }
+
+    fn determinant(&self) -> f64 {
+        self.0[0] * self.0[3] - self.0[1] * self.0[2]
+    }
+
+    /// The circle of `radius` around `(x, y)`, drawn as a dot when `filled`.
+    fn circle(&self, x: f64, y: f64, radius: f64, filled: bool) -> Shape {
+        match filled {
+            true => Shape::Dot {
+                centre: self.apply(x, y),
+            },
+            false => Shape::Circle {
+                centre: self.apply(x, y),
+                radius: radius * self.determinant().abs().sqrt(),
+            },
+        }
+    }
+
+    fn segment(&self, start: (f64, f64), end: (f64, f64)) -> Shape {
+        Shape::Segment {
+            start: self.apply(start.0, start.1),
+            end: self.apply(end.0, end.1),
+        }
+    }
+
+    /// The circular arc of `radius` from `start` to `end` of an SVG path, going through increasing
+    /// angles when `sweep` is set.
+    fn arc(
+        &self,
+        start: (f64, f64),
+        end: (f64, f64),
+        radius: f64,
+        large: bool,
+        sweep: bool,
+    ) -> Shape {
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let chord = dx.hypot(dy);
+        if chord == 0.0 {
+            return self.segment(start, end);
+        }
+
+        let radius = radius.abs().max(chord / 2.0);
+        let height = (radius.powi(2) - (chord / 2.0).powi(2)).max(0.0).sqrt();
+        let side = match large != sweep {
+            true => 1.0,
+            false => -1.0,
+        };
+        let centre = (
+            (start.0 + end.0) / 2.0 - side * height * dy / chord,
+            (start.1 + end.1) / 2.0 + side * height * dx / chord,
+        );
+        let (from, to) = match sweep == (self.determinant() > 0.0) {
+            true => (start, end),
+            false => (end, start),
+        };
+        let centre = self.apply(centre.0, centre.1);
+
+        Shape::arc(
+            centre,
+            radius * self.determinant().abs().sqrt(),
+            (self.apply(from.0, from.1) - centre).phi(),
+            (self.apply(to.0, to.1) - centre).phi(),
+        )
+    }
+}
+
+/// A start tag of an SVG document.
+struct Element<'a> {
+    name: &'a str,
+    attributes: Vec<(&'a str, &'a str)>,
+    closed: bool,
+}
+
+impl<'a> Element<'a> {
+    /// Parses the start tag at the beginning of `text`, returning it with its length in bytes.
+    fn parse(text: &'a str, byte_offset: usize) -> Result<(Element<'a>, usize), ParseSvgError> {
+        let unterminated = || ParseSvgError::new("unterminated tag", byte_offset);
+        let mut quote = None;
+        let length = text
+            .char_indices()
+            .find(|(_, character)| match (quote, character) {
+                (None, '"' | '\'') => {
+                    quote = Some(*character);
+                    false
+                }
+                (Some(open), _) if open == *character => {
+                    quote = None;
+                    false
+                }
+                (None, '>') => true,
+                _ => false,
+            })
+            .map(|(index, _)| index + 1)
+            .ok_or_else(unterminated)?;
+
+        let tag = text[1..length - 1].trim();
+        let closed = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let name_end = tag
+            .find(|character: char| character.is_whitespace())
+            .unwrap_or(tag.len());
+        let mut attributes = Vec::new();
+        let mut rest = tag[name_end..].trim_start();
+
+        while !rest.is_empty() {
+            let (name, value) = rest.split_once('=').ok_or_else(unterminated)?;
+            let value = value.trim_start();
+            let quote = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                Some(_) => return Err(ParseSvgError::new("unquoted attribute value", byte_offset)),
+                None => return Err(unterminated()),
+            };
+            let value_end = value[1..].find(quote).ok_or_else(unterminated)? + 1;
+
+            attributes.push((name.trim(), &value[1..value_end]));
+            rest = value[value_end + 1..].trim_start();
+        }
+
+        // Declarations in the style attribute override the presentation attributes.
+        let styles = attributes
+            .iter()
+            .filter(|(name, _)| *name == "style")
+            .flat_map(|(_, style)| style.split(';'))
+            .filter_map(|declaration| declaration.split_once(':'))
+            .map(|(name, value)| (name.trim(), value.trim()))
+            .collect::<Vec<(&str, &str)>>();
+        attributes.extend(styles);
+
+        Ok((
+            Element {
+                name: &tag[..name_end],
+                attributes,
+                closed,
+            },
+            length,
+        ))
+    }
+
+    fn attribute(&self, name: &str) -> Option<&'a str> {
+        self.attributes
+            .iter()
+            .rev()
+            .find(|(attribute, _)| *attribute == name)
+            .map(|(_, value)| *value)
+    }
+
+    fn number(&self, name: &str, byte_offset: usize) -> Result<f64, ParseSvgError> {
+        self.attribute(name).map_or(Ok(0.0), |value| {
+            value
+                .trim()
+                .trim_end_matches(|character: char| character.is_ascii_alphabetic())
+                .parse()
+                .map_err(|_| ParseSvgError::new(format!("invalid {} attribute", name), byte_offset))
+        })
+    }
+
+    fn to_shapes(&self, scope: &Scope, byte_offset: usize) -> Result<Vec<Shape>, ParseSvgError> {
+        let number = |name: &str| self.number(name, byte_offset);
+        let transform = &scope.transform;
+
+        Ok(match self.name {
+            "circle" => {
+                vec![transform.circle(number("cx")?, number("cy")?, number("r")?, scope.filled)]
+            }
+            "ellipse" => vec![transform.circle(
+                number("cx")?,
+                number("cy")?,
+                (number("rx")? + number("ry")?) / 2.0,
+                scope.filled,
+            )],
+            "line" => vec![transform.segment(
+                (number("x1")?, number("y1")?),
+                (number("x2")?, number("y2")?),
+            )],
+            "polyline" | "polygon" => {
+                let mut points = numbers(self.attribute("points").unwrap_or_default())
+                    .ok_or_else(|| ParseSvgError::new("invalid points attribute", byte_offset))?
+                    .chunks_exact(2)
+                    .map(|point| (point[0], point[1]))
+                    .collect::<Vec<(f64, f64)>>();
+                if self.name == "polygon" {
+                    points.extend(points.first().copied());
+                }
+
+                points
+                    .windows(2)
+                    .map(|points| transform.segment(points[0], points[1]))
+                    .collect()
+            }
+            "path" => path_to_shapes(self.attribute("d").unwrap_or_default(), transform)
+                .ok_or_else(|| ParseSvgError::new("invalid path data", byte_offset))?,
+            _ => Vec::new(),
+        })
+    }
+}
+
+/// Splits a list of numbers separated by whitespace or commas.
+fn numbers(text: &str) -> Option<Vec<f64>> {
+    let mut tokens = PathTokens::new(text);
+    let mut numbers = Vec::new();
+
+    while tokens.skip_separators() {
+        numbers.push(tokens.number()?);
+    }
+
+    Some(numbers)
+}
+
+/// The shapes drawn by SVG path data.
+fn path_to_shapes(data: &str, transform: &Transform) -> Option<Vec<Shape>> {
+    let mut tokens = PathTokens::new(data);
+    let mut shapes = Vec::new();
+    let (mut current, mut subpath_start) = ((0.0, 0.0), (0.0, 0.0));
+    let mut command = None;
+
+    while tokens.skip_separators() {
+        if let Some(next) = tokens.command() {
+            command = Some(next);
+        }
+        let name = command?;
+        let relative = name.is_ascii_lowercase();
+        let offset = match relative {
+            true => current,
+            false => (0.0, 0.0),
+        };
+        let point = |tokens: &mut PathTokens| -> Option<(f64, f64)> {
+            Some((tokens.number()? + offset.0, tokens.number()? + offset.1))
+        };
+
+        let next = match name.to_ascii_uppercase() {
+            'M' => {
+                let next = point(&mut tokens)?;
+                subpath_start = next;
+                // Further coordinate pairs after a move are lines.
+                command = Some(match relative {
+                    true => 'l',
+                    false => 'L',
+                });
+                current = next;
+                continue;
+            }
+            'L' | 'T' => point(&mut tokens)?,
+            'H' => (tokens.number()? + offset.0, current.1),
+            'V' => (current.0, tokens.number()? + offset.1),
+            'Q' | 'S' => {
+                point(&mut tokens)?;
+                point(&mut tokens)?
+            }
+            'C' => {
+                point(&mut tokens)?;
+                point(&mut tokens)?;
+                point(&mut tokens)?
+            }
+            'A' => {
+                let (radius_x, radius_y) = (tokens.number()?, tokens.number()?);
+                tokens.number()?;
+                let (large, sweep) = (tokens.flag()?, tokens.flag()?);
+                let next = point(&mut tokens)?;
+
+                shapes.push(transform.arc(
+                    current,
+                    next,
+                    (radius_x + radius_y) / 2.0,
+                    large,
+                    sweep,
+                ));
+                current = next;
+                continue;
+            }
+            'Z' => {
+                command = None;
+                subpath_start
+            }
+            _ => return None,
+        };
+
+        if next != current {
+            shapes.push(transform.segment(current, next));
+        }
+        current = next;
+    }
+
+    Some(shapes)
+}
+
+/// A cursor over the commands and numbers of SVG path data.
+struct PathTokens<'a> {
+    text: &'a str,
+}
+
+impl<'a> PathTokens<'a> {
+    fn new(text: &'a str) -> PathTokens<'a> {
+        PathTokens { text }
+    }
+
+    /// Skips whitespace and commas, returning whether anything is left.
+    fn skip_separators(&mut self) -> bool {
+        self.text = self
+            .text
+            .trim_start_matches(|character: char| character.is_whitespace() || character == ',');
+        !self.text.is_empty()
+    }
+
+    fn command(&mut self) -> Option<char> {
+        self.skip_separators();
+        let command = self
+            .text
+            .chars()
+            .next()
+            .filter(|command| command.is_ascii_alphabetic() && !matches!(command, 'e' | 'E'))?;
+        self.text = &self.text[1..];
+        Some(command)
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        self.skip_separators();
+        let bytes = self.text.as_bytes();
+        let mut end = 0;
+        let mut seen_point = false;
+
+        while end < bytes.len() {
+            match bytes[end] {
+                b'+' | b'-' if end == 0 || matches!(bytes[end - 1], b'e' | b'E') => {}
+                b'0'..=b'9' => {}
+                b'.' if !seen_point => seen_point = true,
+                b'e' | b'E' if end > 0 => seen_point = true,
+                _ => break,
+            }
+            end += 1;
+        }
+
+        let number = self.text[..end].parse().ok()?;
+        self.text = &self.text[end..];
+        Some(number)
+    }
+
+    fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+        let flag = match self.text.chars().next()? {
+            '0' => false,
+            '1' => true,
+            _ => return None,
+        };
+        self.text = &self.text[1..];
+        Some(flag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unquoted_attribute_value_is_an_error() {
+        let error = svg_to_shapes("<svg><circle cx=“1” cy=\"0\" r=\"3\"/></svg>").unwrap_err();
+
+        assert_eq!(error.reason(), "unquoted attribute value");
+    }
+}
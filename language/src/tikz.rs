@@ -0,0 +1,211 @@
+use crate::colours::{Colour, Colouring};
+use crate::layout::Layout;
+use crate::letters::*;
+use crate::numbers::*;
+use crate::sentences::*;
+use crate::shapes::{BoundingBox, Shape};
+use geomath::vector::Vector2;
+use std::fmt::Write;
+
+/// The space left around the drawing by the background, in glyph units.
+const MARGIN: f64 = 1.0;
+
+/// The presentation attributes of a TikZ picture.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TikzOptions {
+    /// The length of a glyph unit in centimetres.
+    pub scale: f64,
+    /// The width of the strokes in points, which does not change with `scale`.
+    pub line_width: f64,
+    /// The radius of the dots in points, which does not change with `scale`.
+    pub dot_radius: f64,
+    /// The colour of the strokes and dots, or the colour of the surrounding text when missing.
+    pub colour: Option<Colour>,
+    /// The colour filling the box around the drawing, if any.
+    pub background: Option<Colour>,
+    /// Whether to wrap the picture in a `standalone` document that can be compiled on its own.
+    pub standalone: bool,
+}
+
+impl Default for TikzOptions {
+    fn default() -> Self {
+        TikzOptions {
+            scale: 0.25,
+            line_width: 0.4,
+            dot_radius: 1.0,
+            colour: None,
+            background: None,
+            standalone: false,
+        }
+    }
+}
+
+/// Writes a `tikzpicture` of the word around its centre.
+pub fn word_to_tikz(word: &GallifreyanWord, options: &TikzOptions) -> String {
+    shapes_to_tikz(&word.to_shapes(), options)
+}
+
+/// Writes a `tikzpicture` of the sentence around its centre.
+pub fn sentence_to_tikz(sentence: &GallifreyanSentence, options: &TikzOptions) -> String {
+    shapes_to_tikz(&sentence.to_shapes(), options)
+}
+
+/// Writes a `tikzpicture` of the number around its centre.
+pub fn number_to_tikz(number: &GallifreyanNumber, options: &TikzOptions) -> String {
+    shapes_to_tikz(&number.to_shapes(), options)
+}
+
+/// Writes a `tikzpicture` of the shapes, drawing arcs and circles with `\draw` and dots with
+/// `\fill`, so the picture only needs the `tikz` package.
+pub fn shapes_to_tikz(shapes: &[Shape], options: &TikzOptions) -> String {
+    let shapes = shapes
+        .iter()
+        .map(|shape| (*shape, options.colour))
+        .collect::<Vec<(Shape, Option<Colour>)>>();
+
+    tikz_picture(&shapes, options)
+}
+
+/// Writes a `tikzpicture` of the layout like `shapes_to_tikz`, drawing every element in the colour
+/// `colouring` picks for it instead of `options.colour`.
+pub fn layout_to_tikz_with(
+    layout: &Layout,
+    options: &TikzOptions,
+    colouring: &impl Colouring,
+) -> String {
+    let shapes = layout
+        .elements()
+        .iter()
+        .map(|element| (element.shape, Some(colouring.colour(element))))
+        .collect::<Vec<(Shape, Option<Colour>)>>();
+
+    tikz_picture(&shapes, options)
+}
+
+/// Writes a `tikzpicture` of the shapes like `shapes_to_tikz`, drawing each shape in its own
+/// colour instead of `options.colour`.
+pub fn coloured_shapes_to_tikz(shapes: &[(Shape, Colour)], options: &TikzOptions) -> String {
+    let shapes = shapes
+        .iter()
+        .map(|(shape, colour)| (*shape, Some(*colour)))
+        .collect::<Vec<(Shape, Option<Colour>)>>();
+
+    tikz_picture(&shapes, options)
+}
+
+fn tikz_picture(shapes: &[(Shape, Option<Colour>)], options: &TikzOptions) -> String {
+    let point = |point: Vector2| {
+        format!(
+            "({:.4},{:.4})",
+            point.x * options.scale,
+            point.y * options.scale
+        )
+    };
+    let length = |length: f64| format!("{:.4}", length * options.scale);
+
+    let mut tikz = String::new();
+    if options.standalone {
+        let _ = writeln!(
+            tikz,
+            "\\documentclass[tikz]{{standalone}}\n\\begin{{document}}"
+        );
+    }
+    let _ = writeln!(
+        tikz,
+        "\\begin{{tikzpicture}}[line width={:.3}pt, line cap=round, line join=round]",
+        options.line_width
+    );
+
+    let bounds = shapes
+        .iter()
+        .map(|(shape, _)| shape.bounds())
+        .reduce(|bounds, other| bounds.union(&other));
+    if let (Some(background), Some(bounds)) = (options.background, bounds) {
+        let BoundingBox { min, max } = bounds.expand(MARGIN);
+        let _ = writeln!(
+            tikz,
+            "  \\fill{} {} rectangle {};",
+            colour_option(background),
+            point(min),
+            point(max)
+        );
+    }
+
+    shapes.iter().for_each(|&(shape, colour)| {
+        let colour = colour.map_or(String::new(), colour_option);
+
+        let _ = match shape {
+            Shape::Arc {
+                centre,
+                radius,
+                start,
+                end,
+            } => writeln!(
+                tikz,
+                "  \\draw{colour} {} arc[start angle={:.4}, end angle={:.4}, radius={}];",
+                point(centre + Vector2::new(start.cos(), start.sin()) * radius),
+                start.to_degrees(),
+                end.to_degrees(),
+                length(radius)
+            ),
+            Shape::Circle { centre, radius } => writeln!(
+                tikz,
+                "  \\draw{colour} {} circle[radius={}];",
+                point(centre),
+                length(radius)
+            ),
+            Shape::Dot { centre } => writeln!(
+                tikz,
+                "  \\fill{colour} {} circle[radius={:.3}pt];",
+                point(centre),
+                options.dot_radius
+            ),
+            Shape::Segment { start, end } => {
+                writeln!(tikz, "  \\draw{colour} {} -- {};", point(start), point(end))
+            }
+        };
+    });
+
+    let _ = writeln!(tikz, "\\end{{tikzpicture}}");
+    if options.standalone {
+        let _ = writeln!(tikz, "\\end{{document}}");
+    }
+
+    tikz
+}
+
+/// The option drawing in the colour, with the `xcolor` syntax for RGB channels.
+fn colour_option(colour: Colour) -> String {
+    format!(
+        "[color={{rgb,255:red,{};green,{};blue,{}}}]",
+        colour.0, colour.1, colour.2
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn background_is_filled_before_the_drawing() {
+        let options = TikzOptions {
+            background: Some(Colour(0xff, 0xee, 0x00)),
+            ..TikzOptions::default()
+        };
+        let tikz = shapes_to_tikz(
+            &[Shape::Circle {
+                centre: Vector2::new(0.0, 0.0),
+                radius: 4.0,
+            }],
+            &options,
+        );
+        let lines = tikz.lines().collect::<Vec<&str>>();
+
+        assert_eq!(
+            lines[1],
+            "  \\fill[color={rgb,255:red,255;green,238;blue,0}] (-1.2500,-1.2500) rectangle \
+             (1.2500,1.2500);"
+        );
+        assert_eq!(lines[2], "  \\draw (0.0000,0.0000) circle[radius=1.0000];");
+    }
+}
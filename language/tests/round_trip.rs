@@ -0,0 +1,64 @@
+#![cfg(any(feature = "svg", feature = "raster"))]
+
+use language::glyphs::Style;
+use language::letters::GallifreyanWord;
+
+const WORDS: [&str; 7] = [
+    "hello",
+    "doctor",
+    "tardis",
+    "aeon",
+    "gallifrey",
+    "sherlock",
+    "quartz",
+];
+
+#[cfg(feature = "svg")]
+#[test]
+fn words_read_back_from_svg() {
+    use language::svg::{svg_to_word, word_to_svg};
+
+    WORDS.iter().for_each(|text| {
+        let word = text.parse::<GallifreyanWord>().unwrap();
+        let recognised = svg_to_word(&word_to_svg(&word), &Style::default()).unwrap();
+
+        assert_eq!(recognised.to_word().to_text(), text.to_uppercase());
+    });
+}
+
+#[cfg(feature = "raster")]
+#[test]
+fn words_read_back_from_bitmaps() {
+    use geomath::vector::Vector2;
+    use language::raster::bitmap_to_shapes;
+    use language::recognition::recognise_word;
+    use language::shapes::Shape;
+
+    const SIZE: usize = 640;
+    const STROKE_WIDTH: f64 = 1.5;
+    const DOT_RADIUS: f64 = 2.5;
+
+    WORDS.iter().for_each(|text| {
+        let word = text.parse::<GallifreyanWord>().unwrap();
+        let shapes = word.to_shapes();
+        let bound = word.outer_size() + 1.0;
+        let pixel = 2.0 * bound / SIZE as f64;
+
+        let ink = (0..SIZE * SIZE)
+            .map(|index| {
+                let point = Vector2::new(
+                    ((index % SIZE) as f64 + 0.5) * pixel - bound,
+                    bound - ((index / SIZE) as f64 + 0.5) * pixel,
+                );
+
+                shapes.iter().any(|shape| match shape {
+                    Shape::Dot { .. } => shape.distance(point) <= DOT_RADIUS * pixel,
+                    _ => shape.distance(point) <= STROKE_WIDTH / 2.0 * pixel,
+                })
+            })
+            .collect::<Vec<bool>>();
+        let recognised = recognise_word(&bitmap_to_shapes(&ink, SIZE), &Style::default()).unwrap();
+
+        assert_eq!(recognised.to_word().to_text(), text.to_uppercase());
+    });
+}